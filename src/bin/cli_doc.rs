@@ -1,25 +1,740 @@
-use clap::Parser;
-use cli_doc::create_html_doc;
+use anyhow::{Context, bail};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Args as _, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use cli_doc::{
+    Backend, BuiltinParser, CaptureMode, ConsoleReporter, DocBuilder, Document, HelpInvocation,
+    OutputConfig, OutputStream, ProgramDesc, ProgramEnvironment, ProjectConfig, Reporter,
+    RunnerConfig, Verbosity, VersionSource, diff_programs, parse_model,
+};
 use colored::Colorize;
-use std::path::PathBuf;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Format {
+    Html,
+    Json,
+    Markdown,
+    Man,
+}
+
+/// Format of help output
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ParserName {
+    Clap,
+    Click,
+    Argparse,
+    Cobra,
+    Man,
+}
+
+impl From<ParserName> for BuiltinParser {
+    fn from(parser: ParserName) -> Self {
+        match parser {
+            ParserName::Clap => BuiltinParser::Clap,
+            ParserName::Click => BuiltinParser::Click,
+            ParserName::Argparse => BuiltinParser::Argparse,
+            ParserName::Cobra => BuiltinParser::Cobra,
+            ParserName::Man => BuiltinParser::Man,
+        }
+    }
+}
+
+/// Output stream of the program containing help
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Stream {
+    Stdout,
+    Stderr,
+    /// Stdout, or stderr when stdout is empty
+    Auto,
+}
+
+impl From<Stream> for OutputStream {
+    fn from(stream: Stream) -> Self {
+        match stream {
+            Stream::Stdout => OutputStream::Stdout,
+            Stream::Stderr => OutputStream::Stderr,
+            Stream::Auto => OutputStream::Auto,
+        }
+    }
+}
 
 /// Generator of documentation for CLI
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
-    program: PathBuf,
+    #[clap(subcommand)]
+    command: Command,
 
-    #[clap(long, default_value = "doc.html")]
-    output_filename: PathBuf,
+    /// Print only warnings and errors
+    #[clap(long, short, global = true)]
+    quiet: bool,
+
+    /// Print also durations of invocations
+    #[clap(long, short, global = true, conflicts_with = "quiet")]
+    verbose: bool,
+
+    /// Project config file [default: cli_doc.toml when it exists]
+    ///
+    /// It is used when the program (or the output of `render`) is not given on the command line.
+    #[clap(long, global = true, value_name = "FILE")]
+    config: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Extract documentation from the program and render it
+    Build {
+        #[clap(flatten)]
+        output: OutputArgs,
+        /// Remove pages of commands that are not documented anymore (`<program>-*.md`,
+        /// `<program>-*.1`) from the output directory
+        #[clap(long)]
+        prune: bool,
+        #[clap(flatten)]
+        extract: ExtractArgs,
+    },
+    /// Extract documentation from the program into a JSON model
+    Extract {
+        /// Output file
+        #[clap(long, default_value = "doc.json")]
+        output_filename: PathBuf,
+        #[clap(flatten)]
+        extract: ExtractArgs,
+    },
+    /// Render a JSON model created by `extract`
+    Render {
+        #[clap(flatten)]
+        output: OutputArgs,
+        /// Remove pages of commands that are not documented anymore (`<program>-*.md`,
+        /// `<program>-*.1`) from the output directory
+        #[clap(long)]
+        prune: bool,
+        /// JSON model (or HTML page) of the program
+        model: PathBuf,
+    },
+    /// Compare two models (JSON models or HTML pages); exits with code 1 when they differ
+    /// and 2 when a model cannot be read
+    Diff {
+        /// The original model
+        old: PathBuf,
+        /// The new model
+        new: PathBuf,
+    },
+    /// Check that the documentation written by `build` is up to date;
+    /// exits with code 1 when it is not and 2 when the model of `--against` cannot be read
+    Check {
+        #[clap(flatten)]
+        output: OutputArgs,
+        #[clap(flatten)]
+        extract: ExtractArgs,
+        /// Compare the documentation of the program with a committed HTML page or JSON model
+        /// instead of the output files
+        #[clap(long, value_name = "FILE", conflicts_with_all = ["format", "output_filename", "split"])]
+        against: Option<PathBuf>,
+    },
+}
+
+/// Format and location of the documentation
+#[derive(clap::Args, Debug)]
+struct OutputArgs {
+    /// Output format [default: html]
+    #[clap(long, value_enum)]
+    format: Option<Format>,
+
+    /// Output file [default: doc.html, doc.json or doc.md]
+    ///
+    /// For man pages or when `--split` is used, it is a directory [default: man or doc]
+    #[clap(long)]
+    output_filename: Option<PathBuf>,
+
+    /// Write one file per command (only for Markdown; man pages are always one file per command)
+    #[clap(long)]
+    split: bool,
 }
 
-pub fn main() -> anyhow::Result<()> {
-    let args = Args::parse();
-    let svg = create_html_doc(&args.program)?;
-    std::fs::write(&args.output_filename, &svg)?;
-    println!(
-        "Output written into: {}",
-        args.output_filename.display().to_string().green()
-    );
+impl OutputArgs {
+    fn is_set(&self) -> bool {
+        self.format.is_some() || self.output_filename.is_some() || self.split
+    }
+
+    fn output_config(self) -> anyhow::Result<OutputConfig> {
+        let backend = match (self.format.unwrap_or(Format::Html), self.split) {
+            (Format::Html, false) => Backend::Html,
+            (Format::Json, false) => Backend::Json,
+            (Format::Markdown, false) => Backend::Markdown,
+            (Format::Markdown, true) => Backend::MarkdownPages,
+            (Format::Man, false) => Backend::ManPages,
+            (_, true) => bail!("--split is supported only for Markdown format"),
+        };
+        Ok(OutputConfig {
+            format: backend,
+            path: self.output_filename,
+            theme: None,
+            extra_pages: Vec::new(),
+        })
+    }
+
+    /// Outputs given on the command line, or by the config file when none is given;
+    /// `config` is `None` when the program is not the one of the config file
+    fn into_outputs(self, config: Option<&ProjectConfig>) -> anyhow::Result<Vec<OutputConfig>> {
+        match config {
+            Some(config) if !self.is_set() => Ok(config.outputs()),
+            _ => Ok(vec![self.output_config()?]),
+        }
+    }
+}
+
+/// The documented program and the way it is run
+#[derive(clap::Args, Debug)]
+struct ExtractArgs {
+    /// Program to document [default: the program of the config file]
+    ///
+    /// A program run through a launcher is given after `--` with the fixed arguments passed
+    /// before subcommands (e.g. `-- python -m tool` or `-- cargo run --bin tool --`).
+    /// With the config file, only `--jobs`, `--keep-going`, `--record` and `--replay`
+    /// of the options below can be used.
+    #[clap(value_name = "PROGRAM")]
+    program: Option<String>,
+
+    /// After `--`: the program with its fixed arguments, or only the arguments when
+    /// PROGRAM is given before `--`
+    #[clap(value_name = "COMMAND", last = true)]
+    program_command: Vec<String>,
+
+    /// Name of the program in the documentation [default: file name of the program]
+    #[clap(long)]
+    name: Option<String>,
+
+    /// Help parser; detected automatically by default
+    #[clap(long, value_enum)]
+    parser: Option<ParserName>,
+
+    /// Number of help commands run in parallel [default: number of CPUs]
+    #[clap(long, short)]
+    jobs: Option<NonZeroUsize>,
+
+    /// Time limit in seconds for a single invocation of the program (0 = no limit)
+    #[clap(long, default_value_t = 30)]
+    timeout: u64,
+
+    /// Document the rest of the program when help of some command cannot be extracted
+    #[clap(long)]
+    keep_going: bool,
+
+    /// Set an environment variable of the program (can be repeated)
+    #[clap(long, value_name = "NAME=VALUE", value_parser = parse_env_var)]
+    env: Vec<(String, String)>,
+
+    /// Remove an environment variable of the program (can be repeated)
+    #[clap(long, value_name = "NAME")]
+    unset_env: Vec<String>,
+
+    /// Run the program with an empty environment (except `PATH` and variables set by `--env`)
+    #[clap(long)]
+    clear_env: bool,
+
+    /// Terminal width for the program (sets `COLUMNS`)
+    #[clap(long)]
+    columns: Option<usize>,
+
+    /// Disable colors of the program (sets `NO_COLOR=1` and `CLICOLOR=0`)
+    #[clap(long)]
+    no_color: bool,
+
+    /// Run the program in the "C" locale (sets `LANG=C` and `LC_ALL=C`)
+    #[clap(long)]
+    c_locale: bool,
+
+    /// Working directory of the program [default: current directory]
+    #[clap(long)]
+    working_dir: Option<PathBuf>,
+
+    /// Flag printing help of a command (e.g. `-h`, `--help-all`, `-help`)
+    #[clap(long, default_value = "--help", allow_hyphen_values = true)]
+    help_flag: String,
+
+    /// Request help by a subcommand put before the command path instead of a flag
+    /// (e.g. `help` for `prog help sub`)
+    #[clap(long, value_name = "NAME", conflicts_with = "help_flag")]
+    help_command: Option<String>,
+
+    /// Flag printing the version of the program
+    #[clap(long, default_value = "--version", allow_hyphen_values = true)]
+    version_flag: String,
+
+    /// Version of the program; the program is not asked for it
+    #[clap(long, value_name = "VERSION", conflicts_with = "version_flag")]
+    version_string: Option<String>,
+
+    /// Output stream of the program containing help
+    #[clap(long, value_enum, default_value_t = Stream::Stdout)]
+    help_output: Stream,
+
+    /// Accept help printed by the program finishing with a non-zero exit code
+    #[clap(long)]
+    accept_failure: bool,
+
+    /// Store all outputs of the program into a capture file
+    #[clap(long, value_name = "FILE")]
+    record: Option<PathBuf>,
+
+    /// Read outputs of the program from a capture file instead of running it
+    #[clap(long, value_name = "FILE", conflicts_with = "record")]
+    replay: Option<PathBuf>,
+
+    /// Extract only subcommands matching the pattern, e.g. "remote *" (can be repeated)
+    ///
+    /// Patterns are matched against space-separated paths of subcommands word by word;
+    /// `*` and `?` are wildcards. Subcommands of matched commands match too.
+    #[clap(long, value_name = "PATTERN")]
+    include: Vec<String>,
+
+    /// Do not extract subcommands matching the pattern, e.g. "debug *" (can be repeated)
+    ///
+    /// Excluded commands and their subcommands are never run.
+    #[clap(long, value_name = "PATTERN")]
+    exclude: Vec<String>,
+
+    /// Maximal depth of documented subcommands (1 = only direct subcommands of the program)
+    #[clap(long)]
+    max_depth: Option<usize>,
+}
+
+fn parse_env_var(input: &str) -> Result<(String, String), String> {
+    match input.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+        _ => Err(format!("expected NAME=VALUE, got `{input}`")),
+    }
+}
+
+impl ExtractArgs {
+    fn capture(&self) -> CaptureMode {
+        match (&self.record, &self.replay) {
+            (Some(path), _) => CaptureMode::Record(path.clone()),
+            (_, Some(path)) => CaptureMode::Replay(path.clone()),
+            (None, None) => CaptureMode::Disabled,
+        }
+    }
+
+    /// The program followed by its fixed arguments
+    fn command(&self) -> Vec<String> {
+        self.program
+            .iter()
+            .chain(&self.program_command)
+            .cloned()
+            .collect()
+    }
+
+    /// The config file when the program is taken from it
+    fn program_config<'a>(&self, config: Option<&'a ProjectConfig>) -> Option<&'a ProjectConfig> {
+        config.filter(|_| self.command().is_empty())
+    }
+
+    /// Ids of options applied together with the program of the config file
+    const CONFIG_COMPATIBLE: [&str; 6] = [
+        "program",
+        "program_command",
+        "jobs",
+        "keep_going",
+        "record",
+        "replay",
+    ];
+
+    /// Options describing the program that are given on the command line of the subcommand;
+    /// an explicit value equal to the default is reported too
+    fn program_options(matches: &ArgMatches) -> Vec<String> {
+        // Matches of the subcommand contain also output and global options
+        let extract = Self::augment_args(clap::Command::new("extract"));
+        matches
+            .ids()
+            .filter(|id| !Self::CONFIG_COMPATIBLE.contains(&id.as_str()))
+            .filter(|id| matches.value_source(id.as_str()) == Some(ValueSource::CommandLine))
+            .filter_map(|id| extract.get_arguments().find(|arg| arg.get_id() == id))
+            .map(|arg| match arg.get_long() {
+                Some(long) => format!("--{long}"),
+                None => arg.get_id().to_string(),
+            })
+            .collect()
+    }
+
+    fn into_builder(self, program: PathBuf, program_args: Vec<String>) -> DocBuilder {
+        let capture = self.capture();
+        let config = RunnerConfig {
+            program_args,
+            name: self.name,
+            parser: self.parser.map(|p| BuiltinParser::from(p).into()),
+            jobs: self.jobs.map_or(0, NonZeroUsize::get),
+            timeout: (self.timeout > 0).then(|| Duration::from_secs(self.timeout)),
+            keep_going: self.keep_going,
+            help: match self.help_command {
+                Some(name) => HelpInvocation::Command(name),
+                None => HelpInvocation::Flag(self.help_flag),
+            },
+            version: match self.version_string {
+                Some(version) => VersionSource::Fixed(version),
+                None => VersionSource::Flag(self.version_flag),
+            },
+            output_stream: self.help_output.into(),
+            accept_failure: self.accept_failure,
+            capture,
+            include: self.include,
+            exclude: self.exclude,
+            max_depth: self.max_depth,
+            reporter: None,
+            environment: ProgramEnvironment {
+                clear: self.clear_env,
+                remove: self.unset_env,
+                vars: self.env,
+                columns: self.columns,
+                no_color: self.no_color,
+                c_locale: self.c_locale,
+                working_dir: self.working_dir,
+            },
+        };
+        DocBuilder::new(program).config(config)
+    }
+
+    /// Extracts the program given on the command line, or by the config file
+    fn extract(
+        self,
+        matches: &ArgMatches,
+        config: Option<&ProjectConfig>,
+        reporter: Arc<dyn Reporter>,
+    ) -> anyhow::Result<ProgramDesc> {
+        let mut command = self.command().into_iter();
+        let (builder, overrides) = match (command.next(), config) {
+            (Some(program), _) if program.starts_with('-') => {
+                bail!("A program starting with '-' needs a path, e.g. './{program}'")
+            }
+            (Some(program), _) => (self.into_builder(program.into(), command.collect()), None),
+            (None, Some(config)) => {
+                let options = Self::program_options(matches);
+                if !options.is_empty() {
+                    bail!(
+                        "{} cannot be used with the program of {}; set it in the config file \
+                         or give the program on the command line",
+                        options.join(", "),
+                        ProjectConfig::FILENAME
+                    );
+                }
+                let mut builder = config.builder()?.capture(self.capture());
+                if let Some(jobs) = self.jobs {
+                    builder = builder.jobs(jobs.get());
+                }
+                if self.keep_going {
+                    builder = builder.keep_going(true);
+                }
+                (builder, Some(config))
+            }
+            (None, None) => bail!(
+                "No program is given and there is no {} in the current directory",
+                ProjectConfig::FILENAME
+            ),
+        };
+        let mut program = builder.reporter(reporter).extract()?;
+        if let Some(config) = overrides {
+            config.apply_overrides(&mut program)?;
+        }
+        Ok(program)
+    }
+}
+
+fn load_config(path: Option<&Path>) -> anyhow::Result<Option<ProjectConfig>> {
+    match path {
+        Some(path) => ProjectConfig::load(path).map(Some),
+        None => ProjectConfig::discover(),
+    }
+}
+
+/// Rendered outputs with their paths
+fn render_outputs(
+    program: &ProgramDesc,
+    outputs: &[OutputConfig],
+    reporter: &dyn Reporter,
+) -> anyhow::Result<Vec<(PathBuf, Document)>> {
+    outputs
+        .iter()
+        .map(|output| {
+            reporter.rendering(output.format.name());
+            Ok((output.path(), output.render(program)?))
+        })
+        .collect()
+}
+
+fn read_model(path: &Path) -> anyhow::Result<ProgramDesc> {
+    let input = std::fs::read_to_string(path)
+        .with_context(|| format!("Cannot read model {}", path.display()))?;
+    parse_model(&input).with_context(|| format!("Invalid model {}", path.display()))
+}
+
+/// Prints differences of the committed documentation and the current program;
+/// returns false when there are none
+fn print_changes(committed: &ProgramDesc, program: &ProgramDesc) -> bool {
+    let changes = diff_programs(committed, program);
+    for change in &changes {
+        println!("{change}");
+    }
+    !changes.is_empty()
+}
+
+fn write_document(
+    document: &Document,
+    output_filename: &Path,
+    prune: bool,
+    reporter: &dyn Reporter,
+    verbosity: Verbosity,
+) -> anyhow::Result<()> {
+    document.write_to_path(output_filename)?;
+    if prune {
+        document.remove_obsolete_pages(output_filename, reporter)?;
+    }
+    if verbosity >= Verbosity::Normal {
+        match document {
+            Document::File(_) => eprintln!(
+                "Output written into: {}",
+                output_filename.display().to_string().green()
+            ),
+            Document::Pages(pages) => eprintln!(
+                "{} files written into: {}",
+                pages.len(),
+                output_filename.display().to_string().green()
+            ),
+        }
+    }
     Ok(())
 }
+
+/// Exit code of `diff` and `check` when a model cannot be read, so it is not mistaken
+/// for differences (exit code 1)
+const EXIT_INVALID_MODEL: u8 = 2;
+
+fn invalid_model(error: anyhow::Error) -> ExitCode {
+    eprintln!("{} {error:?}", "Error:".red());
+    ExitCode::from(EXIT_INVALID_MODEL)
+}
+
+pub fn main() -> anyhow::Result<ExitCode> {
+    let matches = Args::command().get_matches();
+    let args = Args::from_arg_matches(&matches)?;
+    // Matches of the subcommand, e.g. for telling which options were given
+    let matches = matches.subcommand().map_or(&matches, |(_, m)| m);
+    let verbosity = if args.quiet {
+        Verbosity::Quiet
+    } else if args.verbose {
+        Verbosity::Verbose
+    } else {
+        Verbosity::Normal
+    };
+    let reporter = Arc::new(ConsoleReporter::new(verbosity));
+    let config = load_config(args.config.as_deref())?;
+    match args.command {
+        Command::Build {
+            output,
+            prune,
+            extract,
+        } => {
+            let outputs = output.into_outputs(extract.program_config(config.as_ref()))?;
+            let program = extract.extract(matches, config.as_ref(), reporter.clone())?;
+            for (path, document) in render_outputs(&program, &outputs, reporter.as_ref())? {
+                write_document(&document, &path, prune, reporter.as_ref(), verbosity)?;
+            }
+        }
+        Command::Extract {
+            output_filename,
+            extract,
+        } => {
+            let program = extract.extract(matches, config.as_ref(), reporter.clone())?;
+            let output = OutputConfig {
+                format: Backend::Json,
+                path: Some(output_filename),
+                theme: None,
+                extra_pages: Vec::new(),
+            };
+            write_document(
+                &output.render(&program)?,
+                &output.path(),
+                false,
+                reporter.as_ref(),
+                verbosity,
+            )?;
+        }
+        Command::Render {
+            output,
+            prune,
+            model,
+        } => {
+            let program = read_model(&model)?;
+            let outputs = output.into_outputs(config.as_ref())?;
+            for (path, document) in render_outputs(&program, &outputs, reporter.as_ref())? {
+                write_document(&document, &path, prune, reporter.as_ref(), verbosity)?;
+            }
+        }
+        Command::Diff { old, new } => {
+            let (old, new) = match (read_model(&old), read_model(&new)) {
+                (Ok(old), Ok(new)) => (old, new),
+                (Err(error), _) | (_, Err(error)) => return Ok(invalid_model(error)),
+            };
+            if print_changes(&old, &new) {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        Command::Check {
+            output,
+            extract,
+            against,
+        } => {
+            let outputs = output.into_outputs(extract.program_config(config.as_ref()))?;
+            let program = extract.extract(matches, config.as_ref(), reporter.clone())?;
+            if let Some(against) = against {
+                let committed = match read_model(&against) {
+                    Ok(committed) => committed,
+                    Err(error) => return Ok(invalid_model(error)),
+                };
+                if print_changes(&committed, &program) {
+                    eprintln!(
+                        "{} {} does not match the help of the program",
+                        "Error:".red(),
+                        against.display()
+                    );
+                    return Ok(ExitCode::FAILURE);
+                }
+                if verbosity >= Verbosity::Normal {
+                    eprintln!("Documentation is up to date");
+                }
+                return Ok(ExitCode::SUCCESS);
+            }
+            let mut stale_files = Vec::new();
+            let mut obsolete_pages = Vec::new();
+            for (path, document) in render_outputs(&program, &outputs, reporter.as_ref())? {
+                stale_files.extend(document.stale_files(&path)?);
+                obsolete_pages.extend(document.obsolete_pages(&path)?);
+            }
+            if !stale_files.is_empty() {
+                for path in &stale_files {
+                    if obsolete_pages.contains(path) {
+                        eprintln!(
+                            "{} {} documents a removed command",
+                            "Error:".red(),
+                            path.display()
+                        );
+                        continue;
+                    }
+                    eprintln!("{} {} is not up to date", "Error:".red(), path.display());
+                    // HTML pages and JSON models contain the model, so the changes can be shown
+                    if let Ok(committed) = read_model(path)
+                        && !print_changes(&committed, &program)
+                    {
+                        eprintln!(
+                            "The documented commands are the same; the file differs in formatting \
+                             (e.g. it was written by another version of cli_doc)"
+                        );
+                    }
+                }
+                eprintln!(
+                    "Run `cli_doc build` with the same arguments to update the documentation{}",
+                    if obsolete_pages.is_empty() {
+                        ""
+                    } else {
+                        " (and `--prune` to remove pages of removed commands)"
+                    }
+                );
+                return Ok(ExitCode::FAILURE);
+            }
+            if verbosity >= Verbosity::Normal {
+                eprintln!("Documentation is up to date");
+            }
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_extract_args(args: &[&str]) -> ExtractArgs {
+        let args = Args::try_parse_from(["cli_doc", "build"].iter().chain(args)).unwrap();
+        let Command::Build { extract, .. } = args.command else {
+            panic!("build expected")
+        };
+        extract
+    }
+
+    fn parse_command(args: &[&str]) -> Vec<String> {
+        parse_extract_args(args).command()
+    }
+
+    #[test]
+    fn test_program_args() {
+        assert_eq!(parse_command(&["python"]), ["python"]);
+        assert_eq!(
+            parse_command(&["--", "python", "-m", "tool"]),
+            ["python", "-m", "tool"]
+        );
+        assert_eq!(
+            parse_command(&["python", "--", "-m", "tool"]),
+            ["python", "-m", "tool"]
+        );
+        assert_eq!(
+            parse_command(&[
+                "--name", "tool", "--", "cargo", "run", "--bin", "tool", "--"
+            ]),
+            ["cargo", "run", "--bin", "tool", "--"]
+        );
+        // Options after the program belong to cli_doc
+        let extract = parse_extract_args(&["tool", "--name", "x", "--keep-going"]);
+        assert_eq!(extract.command(), ["tool"]);
+        assert_eq!(extract.name.as_deref(), Some("x"));
+        assert!(extract.keep_going);
+        let args =
+            Args::try_parse_from(["cli_doc", "build", "cargo", "--output-filename", "x"]).unwrap();
+        let Command::Build {
+            output, extract, ..
+        } = args.command
+        else {
+            panic!("build expected")
+        };
+        assert_eq!(output.output_filename, Some(PathBuf::from("x")));
+        assert_eq!(extract.command(), ["cargo"]);
+        // Arguments of the program need `--`
+        assert!(Args::try_parse_from(["cli_doc", "build", "python", "-m", "tool"]).is_err());
+        assert!(parse_command(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_program_options() {
+        let program_options = |args: &[&str]| {
+            let matches = Args::command()
+                .try_get_matches_from(["cli_doc", "build"].iter().chain(args))
+                .unwrap();
+            ExtractArgs::program_options(matches.subcommand_matches("build").unwrap())
+        };
+        // Options of the output and global options are not a part of the program
+        assert!(
+            program_options(&[
+                "--jobs",
+                "2",
+                "--keep-going",
+                "--record",
+                "capture.json",
+                "--format",
+                "json",
+                "--prune",
+                "--quiet",
+            ])
+            .is_empty()
+        );
+        assert_eq!(
+            program_options(&["--name", "tool", "--timeout", "5", "--help-output", "auto"]),
+            ["--name", "--timeout", "--help-output"]
+        );
+        assert_eq!(
+            program_options(&["--timeout", "30", "--help-flag", "--help", "--env", "A=1"]),
+            ["--timeout", "--help-flag", "--env"]
+        );
+    }
+}
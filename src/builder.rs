@@ -0,0 +1,448 @@
+use crate::commands::ProgramDesc;
+use crate::extractor::capture::CaptureMode;
+use crate::extractor::environment::ProgramEnvironment;
+use crate::extractor::invocation::{HelpInvocation, OutputStream, VersionSource};
+use crate::extractor::parser::HelpParser;
+use crate::extractor::runner::{RunnerConfig, collect_program_info};
+use crate::html::render_html;
+use crate::json::{render_compact_json, render_json};
+use crate::man::render_man_pages;
+use crate::markdown::{render_markdown, render_markdown_pages};
+use crate::reporter::Reporter;
+use anyhow::bail;
+use serde::Deserialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A single output file of a multi-file backend
+#[derive(Debug, Clone)]
+pub struct DocPage {
+    pub filename: String,
+    pub content: String,
+}
+
+/// Output format of the documentation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Backend {
+    /// Single self-contained HTML page
+    Html,
+    /// The extracted model as JSON
+    Json,
+    /// Single Markdown document
+    Markdown,
+    /// One Markdown file per command
+    MarkdownPages,
+    /// One man page per command
+    ManPages,
+}
+
+impl Backend {
+    /// Human-readable name used in progress messages
+    pub fn name(&self) -> &'static str {
+        match self {
+            Backend::Html => "HTML",
+            Backend::Json => "JSON",
+            Backend::Markdown | Backend::MarkdownPages => "Markdown",
+            Backend::ManPages => "man pages",
+        }
+    }
+
+    /// Output file, or directory for backends producing multiple pages
+    pub fn default_filename(&self) -> &'static str {
+        match self {
+            Backend::Html => "doc.html",
+            Backend::Json => "doc.json",
+            Backend::Markdown => "doc.md",
+            Backend::MarkdownPages => "doc",
+            Backend::ManPages => "man",
+        }
+    }
+}
+
+/// Rendered documentation
+#[derive(Debug, Clone)]
+pub enum Document {
+    File(String),
+    Pages(Vec<DocPage>),
+}
+
+impl Document {
+    /// Writes a single-file document; multi-file documents need `write_to_path`
+    pub fn write_to(&self, mut out: impl Write) -> anyhow::Result<()> {
+        match self {
+            Document::File(content) => out.write_all(content.as_bytes())?,
+            Document::Pages(_) => {
+                bail!("Documentation with multiple pages has to be written into a directory")
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a single-file document into the file, or pages into the directory;
+    /// other files in the directory are kept (see `remove_obsolete_pages`)
+    pub fn write_to_path(&self, path: &Path) -> anyhow::Result<()> {
+        match self {
+            Document::File(content) => std::fs::write(path, content)?,
+            Document::Pages(pages) => {
+                std::fs::create_dir_all(path)?;
+                for page in pages {
+                    std::fs::write(path.join(&page.filename), &page.content)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Files in the directory named like a page of the program (`<program>.<ext>` or
+    /// `<program>-*.<ext>`) that are not in the document, e.g. pages of removed commands
+    pub fn obsolete_pages(&self, path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        match self {
+            Document::File(_) => Ok(Vec::new()),
+            Document::Pages(pages) => obsolete_pages(pages, path),
+        }
+    }
+
+    /// Removes `obsolete_pages` from the directory; each removed file is reported
+    pub fn remove_obsolete_pages(
+        &self,
+        path: &Path,
+        reporter: &dyn Reporter,
+    ) -> anyhow::Result<()> {
+        for obsolete in self.obsolete_pages(path)? {
+            std::fs::remove_file(&obsolete)?;
+            reporter.file_removed(&obsolete);
+        }
+        Ok(())
+    }
+
+    /// Files that are missing or differ from the document, and `obsolete_pages`
+    pub fn stale_files(&self, path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        let is_stale = |path: &Path, content: &str| {
+            std::fs::read_to_string(path).map_or(true, |current| current != content)
+        };
+        Ok(match self {
+            Document::File(content) => {
+                if is_stale(path, content) {
+                    vec![path.to_path_buf()]
+                } else {
+                    Vec::new()
+                }
+            }
+            Document::Pages(pages) => {
+                let mut stale: Vec<_> = pages
+                    .iter()
+                    .map(|page| path.join(&page.filename))
+                    .zip(pages)
+                    .filter(|(path, page)| is_stale(path, &page.content))
+                    .map(|(path, _)| path)
+                    .collect();
+                stale.extend(obsolete_pages(pages, path)?);
+                stale
+            }
+        })
+    }
+}
+
+/// Files in the directory that are not among the pages, but are named like a page of
+/// the program; pages of other programs and other files are not included
+fn obsolete_pages(pages: &[DocPage], dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    // The first page documents the program itself
+    let Some((program, extension)) = pages
+        .first()
+        .and_then(|page| page.filename.rsplit_once('.'))
+    else {
+        return Ok(Vec::new());
+    };
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let is_program_page = |filename: &str| {
+        filename
+            .strip_suffix(extension)
+            .and_then(|f| f.strip_suffix('.'))
+            .and_then(|f| f.strip_prefix(program))
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+    };
+    let mut obsolete = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let Some(filename) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        let is_page = pages.iter().any(|page| filename == page.filename);
+        if !is_page && is_program_page(&filename) && entry.file_type()?.is_file() {
+            obsolete.push(entry.path());
+        }
+    }
+    obsolete.sort();
+    Ok(obsolete)
+}
+
+/// Options of rendering that are not a part of the model
+#[derive(Debug, Clone, Default)]
+pub struct RenderOptions {
+    /// CSS appended to the default style of the HTML page
+    pub theme: Option<String>,
+}
+
+/// Renders the program by the backend; options repeated from parent commands are
+/// omitted in all backends except JSON, which keeps the model as extracted
+pub fn render(program: &ProgramDesc, backend: Backend) -> anyhow::Result<Document> {
+    render_with_options(program, backend, &RenderOptions::default())
+}
+
+/// Like `render`, with options (e.g. a theme) applied where the backend supports them
+pub fn render_with_options(
+    program: &ProgramDesc,
+    backend: Backend,
+    options: &RenderOptions,
+) -> anyhow::Result<Document> {
+    if backend == Backend::Json {
+        return Ok(Document::File(render_json(program)?));
+    }
+    let model_json = render_compact_json(program)?;
+    let mut program = program.clone();
+    program.command.prune_repeated_options();
+    Ok(match backend {
+        Backend::Html => Document::File(render_html(
+            &program,
+            &model_json,
+            options.theme.as_deref(),
+        )?),
+        Backend::Json => unreachable!(),
+        Backend::Markdown => Document::File(render_markdown(&program)),
+        Backend::MarkdownPages => Document::Pages(render_markdown_pages(&program)),
+        Backend::ManPages => Document::Pages(render_man_pages(&program)),
+    })
+}
+
+/// Configuration of the extraction of documentation from a program
+///
+/// ```no_run
+/// use cli_doc::{Backend, BuiltinParser, DocBuilder};
+///
+/// let builder = DocBuilder::new("cargo")
+///     .parser(BuiltinParser::Clap)
+///     .jobs(4)
+///     .keep_going(true);
+/// let program = builder.extract()?;
+/// cli_doc::render(&program, Backend::Html)?.write_to_path("doc.html".as_ref())?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub struct DocBuilder {
+    program: PathBuf,
+    config: RunnerConfig,
+}
+
+impl DocBuilder {
+    pub fn new(program: impl Into<PathBuf>) -> Self {
+        DocBuilder {
+            program: program.into(),
+            config: RunnerConfig::default(),
+        }
+    }
+
+    /// Replaces the whole configuration of the runner
+    pub fn config(mut self, config: RunnerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Fixed arguments passed to the program before subcommands (e.g. `-m tool`)
+    pub fn program_args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.config.program_args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.config.name = Some(name.into());
+        self
+    }
+
+    /// Parser of help of all commands, a `BuiltinParser` or a custom `HelpParser`;
+    /// the format is detected for each command by default
+    pub fn parser(mut self, parser: impl Into<Arc<dyn HelpParser>>) -> Self {
+        self.config.parser = Some(parser.into());
+        self
+    }
+
+    /// Number of help commands run in parallel; 0 means the number of CPUs
+    pub fn jobs(mut self, jobs: usize) -> Self {
+        self.config.jobs = jobs;
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.config.timeout = timeout;
+        self
+    }
+
+    pub fn keep_going(mut self, keep_going: bool) -> Self {
+        self.config.keep_going = keep_going;
+        self
+    }
+
+    pub fn environment(mut self, environment: ProgramEnvironment) -> Self {
+        self.config.environment = environment;
+        self
+    }
+
+    pub fn help(mut self, help: HelpInvocation) -> Self {
+        self.config.help = help;
+        self
+    }
+
+    pub fn version(mut self, version: VersionSource) -> Self {
+        self.config.version = version;
+        self
+    }
+
+    pub fn output_stream(mut self, output_stream: OutputStream) -> Self {
+        self.config.output_stream = output_stream;
+        self
+    }
+
+    pub fn accept_failure(mut self, accept_failure: bool) -> Self {
+        self.config.accept_failure = accept_failure;
+        self
+    }
+
+    pub fn capture(mut self, capture: CaptureMode) -> Self {
+        self.config.capture = capture;
+        self
+    }
+
+    pub fn include(mut self, pattern: impl Into<String>) -> Self {
+        self.config.include.push(pattern.into());
+        self
+    }
+
+    pub fn exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.config.exclude.push(pattern.into());
+        self
+    }
+
+    pub fn max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.config.max_depth = max_depth;
+        self
+    }
+
+    pub fn reporter(mut self, reporter: Arc<dyn Reporter>) -> Self {
+        self.config.reporter = Some(reporter);
+        self
+    }
+
+    /// Runs the program and returns the extracted model
+    pub fn extract(&self) -> anyhow::Result<ProgramDesc> {
+        collect_program_info(&self.program, &self.config)
+    }
+
+    /// Extracts the model and renders it by the backend
+    pub fn build(&self, backend: Backend) -> anyhow::Result<Document> {
+        let program = self.extract()?;
+        self.config.reporter().rendering(backend.name());
+        render(&program, backend)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::fixtures::{make_command, make_program};
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_render() {
+        let program = make_program(make_command(
+            "prog",
+            vec![],
+            vec![make_command("run", vec![], vec![])],
+        ));
+        let mut out = Vec::new();
+        render(&program, Backend::Markdown)
+            .unwrap()
+            .write_to(&mut out)
+            .unwrap();
+        let markdown = String::from_utf8(out).unwrap();
+        assert!(markdown.contains("Brief of run"));
+
+        let json = render(&program, Backend::Json).unwrap();
+        let Document::File(json) = json else {
+            panic!("JSON is a single file")
+        };
+        assert_eq!(crate::json::parse_json(&json).unwrap(), program);
+
+        let pages = render(&program, Backend::ManPages).unwrap();
+        assert!(pages.write_to(Vec::new()).is_err());
+        let Document::Pages(pages) = pages else {
+            panic!("man pages are multiple files")
+        };
+        let filenames: Vec<_> = pages.iter().map(|p| p.filename.as_str()).collect();
+        assert_eq!(filenames, ["prog.1", "prog-run.1"]);
+    }
+
+    #[derive(Default)]
+    struct RemovalReporter {
+        removed: Mutex<Vec<PathBuf>>,
+    }
+
+    impl Reporter for RemovalReporter {
+        fn file_removed(&self, path: &Path) {
+            self.removed.lock().unwrap().push(path.to_path_buf());
+        }
+    }
+
+    #[test]
+    fn test_stale_pages() {
+        for (backend, extension) in [(Backend::ManPages, "1"), (Backend::MarkdownPages, "md")] {
+            let dir = std::env::temp_dir()
+                .join(format!("cli_doc_pages_{}_{extension}", std::process::id()));
+            let program = make_program(make_command(
+                "prog",
+                vec![],
+                vec![
+                    make_command("run", vec![], vec![]),
+                    make_command("old", vec![], vec![]),
+                ],
+            ));
+            render(&program, backend)
+                .unwrap()
+                .write_to_path(&dir)
+                .unwrap();
+            let foreign = [
+                dir.join("README.md"),
+                dir.join(format!("other.{extension}")),
+                dir.join(format!("program.{extension}")),
+            ];
+            for path in &foreign {
+                std::fs::write(path, "Not a page").unwrap();
+            }
+
+            let mut program = program;
+            program.command.commands.pop();
+            let document = render(&program, backend).unwrap();
+            // The page of the program lists its subcommands
+            assert_eq!(
+                document.stale_files(&dir).unwrap(),
+                [
+                    dir.join(format!("prog.{extension}")),
+                    dir.join(format!("prog-old.{extension}"))
+                ]
+            );
+            document.write_to_path(&dir).unwrap();
+            // Pages of removed commands are only removed on request
+            let old = dir.join(format!("prog-old.{extension}"));
+            assert_eq!(document.stale_files(&dir).unwrap(), [old.as_path()]);
+            let reporter = RemovalReporter::default();
+            document.remove_obsolete_pages(&dir, &reporter).unwrap();
+            assert_eq!(*reporter.removed.lock().unwrap(), [old.as_path()]);
+            assert!(document.stale_files(&dir).unwrap().is_empty());
+            assert!(!old.exists());
+            assert!(foreign.iter().all(|path| path.exists()));
+            std::fs::remove_dir_all(&dir).unwrap();
+        }
+    }
+}
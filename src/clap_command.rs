@@ -0,0 +1,512 @@
+use crate::commands::{
+    ArgGroupDesc, ArgumentDesc, CategoryDesc, CommandDesc, CommandDoc, CommandId, OptionDesc,
+    PossibleValue, ProgramDesc, Usage, ValueInfo,
+};
+use crate::extractor::clap_parser::parse_usage;
+use crate::text::RichText;
+use clap::{Arg, Command, ValueHint};
+
+/// Title of options without an explicit help heading, as in clap's help
+const DEFAULT_HEADING: &str = "Options";
+
+fn paragraphs(text: &str) -> Vec<Vec<&str>> {
+    text.split("\n\n")
+        .map(|p| {
+            p.lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+/// Splits help into a brief and a description like clap does for `--help`:
+/// the long text is used when present and its first paragraph is the brief
+fn brief_and_description(
+    short: Option<String>,
+    long: Option<String>,
+) -> (RichText, Option<RichText>) {
+    let long_paragraphs = long.as_deref().map(paragraphs).unwrap_or_default();
+    let mut brief = RichText::new();
+    let mut rest = long_paragraphs.as_slice();
+    match short.as_deref() {
+        Some(short) => {
+            for paragraph in paragraphs(short) {
+                brief.add_lines(&paragraph);
+            }
+            if rest.first().is_some_and(|p| {
+                p.join(" ") == short.split_whitespace().collect::<Vec<_>>().join(" ")
+            }) {
+                rest = &rest[1..];
+            }
+        }
+        None => {
+            if let Some((first, tail)) = rest.split_first() {
+                brief.add_lines(first);
+                rest = tail;
+            }
+        }
+    }
+    let mut description = RichText::new();
+    for paragraph in rest {
+        description.add_lines(paragraph);
+    }
+    (
+        brief,
+        if description.is_empty() {
+            None
+        } else {
+            Some(description)
+        },
+    )
+}
+
+fn is_hidden(arg: &Arg) -> bool {
+    arg.is_hide_set() || arg.is_hide_long_help_set()
+}
+
+fn takes_values(arg: &Arg) -> bool {
+    arg.get_action().takes_values()
+}
+
+fn accepts_multiple(arg: &Arg) -> bool {
+    arg.get_num_args()
+        .is_some_and(|range| range.max_values() > 1)
+        || matches!(arg.get_action(), clap::ArgAction::Append)
+}
+
+fn value_names(arg: &Arg) -> Vec<String> {
+    match arg.get_value_names() {
+        Some(names) if !names.is_empty() => names.iter().map(|n| n.to_string()).collect(),
+        _ => vec![arg.get_id().to_string()],
+    }
+}
+
+/// E.g. " <WHEN>" or " [<N>...]"
+fn value_suffix(arg: &Arg) -> String {
+    if !takes_values(arg) {
+        return String::new();
+    }
+    let names = value_names(arg);
+    let mut value = names
+        .iter()
+        .map(|n| format!("<{n}>"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if names.len() == 1 && accepts_multiple(arg) {
+        value.push_str("...");
+    }
+    let optional = arg
+        .get_num_args()
+        .is_some_and(|range| range.min_values() == 0);
+    match (optional, arg.is_require_equals_set()) {
+        (true, true) => format!("[={value}]"),
+        (true, false) => format!(" [{value}]"),
+        (false, true) => format!("={value}"),
+        (false, false) => format!(" {value}"),
+    }
+}
+
+fn value_hint(hint: ValueHint) -> Option<&'static str> {
+    Some(match hint {
+        ValueHint::AnyPath => "path",
+        ValueHint::FilePath => "file path",
+        ValueHint::DirPath => "directory path",
+        ValueHint::ExecutablePath => "executable path",
+        ValueHint::CommandName => "command name",
+        ValueHint::CommandString => "command",
+        ValueHint::CommandWithArguments => "command with arguments",
+        ValueHint::Username => "user name",
+        ValueHint::Hostname => "host name",
+        ValueHint::Url => "URL",
+        ValueHint::EmailAddress => "e-mail address",
+        _ => return None,
+    })
+}
+
+fn value_info(arg: &Arg) -> ValueInfo {
+    let mut info = ValueInfo::default();
+    if takes_values(arg) {
+        info.value_name = value_names(arg).into_iter().next();
+        if !arg.is_hide_default_value_set() && !arg.get_default_values().is_empty() {
+            info.default = Some(
+                arg.get_default_values()
+                    .iter()
+                    .map(|v| v.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            );
+        }
+        if !arg.is_hide_possible_values_set() {
+            info.possible_values = arg
+                .get_possible_values()
+                .iter()
+                .filter(|v| !v.is_hide_set())
+                .map(|v| PossibleValue {
+                    name: v.get_name().to_string(),
+                    help: v.get_help().map(|help| help.to_string()),
+                })
+                .collect();
+        }
+        info.value_hint = value_hint(arg.get_value_hint()).map(str::to_string);
+    }
+    if !arg.is_hide_env_set() {
+        info.env = arg.get_env().map(|env| env.to_string_lossy().to_string());
+    }
+    if let Some(aliases) = arg.get_visible_aliases() {
+        info.aliases
+            .extend(aliases.iter().map(|a| format!("--{a}")));
+    }
+    if let Some(aliases) = arg.get_visible_short_aliases() {
+        info.aliases.extend(aliases.iter().map(|a| format!("-{a}")));
+    }
+    info
+}
+
+fn argument_desc(arg: &Arg) -> ArgumentDesc {
+    let mut name = value_names(arg).join(" ");
+    if accepts_multiple(arg) {
+        name.push_str("...");
+    }
+    let name = if arg.is_required_set() {
+        format!("<{name}>")
+    } else {
+        format!("[{name}]")
+    };
+    let (brief, description) = brief_and_description(
+        arg.get_help().map(|s| s.to_string()),
+        arg.get_long_help().map(|s| s.to_string()),
+    );
+    let mut info = value_info(arg);
+    // As for parsed help, the value name of an argument is its name without brackets
+    info.value_name = Some(value_names(arg).join(" "));
+    ArgumentDesc {
+        name,
+        brief,
+        description,
+        info,
+    }
+}
+
+fn option_desc(arg: &Arg) -> OptionDesc {
+    let value = value_suffix(arg);
+    let (short, long) = match (arg.get_short(), arg.get_long()) {
+        (Some(s), Some(l)) => (Some(format!("-{s}")), format!("--{l}{value}")),
+        (None, Some(l)) => (None, format!("--{l}{value}")),
+        (Some(s), None) => (None, format!("-{s}{value}")),
+        (None, None) => (None, format!("{}{value}", arg.get_id())),
+    };
+    let (brief, description) = brief_and_description(
+        arg.get_help().map(|s| s.to_string()),
+        arg.get_long_help().map(|s| s.to_string()),
+    );
+    OptionDesc {
+        short,
+        long,
+        brief,
+        description,
+        info: value_info(arg),
+    }
+}
+
+/// Options grouped by help headings; the default heading goes first, the others
+/// in the order of their first use
+fn option_categories(command: &Command) -> Vec<CategoryDesc> {
+    let mut args: Vec<&Arg> = command
+        .get_arguments()
+        .filter(|a| !a.is_positional() && !is_hidden(a))
+        .collect();
+    args.sort_by_key(|a| a.get_display_order());
+    let mut categories: Vec<CategoryDesc> = Vec::new();
+    for arg in args {
+        let title = arg.get_help_heading().unwrap_or(DEFAULT_HEADING);
+        let option = option_desc(arg);
+        match categories.iter_mut().find(|c| c.title == title) {
+            Some(category) => category.options.push(option),
+            None => categories.push(CategoryDesc {
+                title: title.to_string(),
+                options: vec![option],
+            }),
+        }
+    }
+    if let Some(idx) = categories.iter().position(|c| c.title == DEFAULT_HEADING) {
+        let category = categories.remove(idx);
+        categories.insert(0, category);
+    }
+    categories
+}
+
+/// Name of an option or an argument in a group, e.g. "--json" or "<FILE>"
+fn member_name(arg: &Arg) -> String {
+    if arg.is_positional() {
+        return argument_desc(arg).name;
+    }
+    match (arg.get_long(), arg.get_short()) {
+        (Some(l), _) => format!("--{l}"),
+        (None, Some(s)) => format!("-{s}"),
+        (None, None) => arg.get_id().to_string(),
+    }
+}
+
+/// Groups constraining their members; groups of clap derive (`#[derive(Args)]`) that only
+/// collect fields of a struct do not restrict anything, so they are omitted
+fn groups(command: &Command) -> Vec<ArgGroupDesc> {
+    command
+        .get_groups()
+        .filter_map(|group| {
+            // `ArgGroup::is_multiple` takes `&mut self`
+            let multiple = group.clone().is_multiple();
+            if multiple && !group.is_required_set() {
+                return None;
+            }
+            let members: Vec<_> = group
+                .get_args()
+                .filter_map(|id| command.get_arguments().find(|a| a.get_id() == id))
+                .filter(|a| !is_hidden(a))
+                .map(member_name)
+                .collect();
+            (!members.is_empty()).then(|| ArgGroupDesc {
+                name: group.get_id().to_string(),
+                members,
+                required: group.is_required_set(),
+                multiple,
+            })
+        })
+        .collect()
+}
+
+fn usage(command: &Command) -> Vec<Usage> {
+    let rendered = command.clone().render_usage().to_string();
+    let rendered = rendered.strip_prefix("Usage:").unwrap_or(&rendered);
+    rendered
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(parse_usage)
+        .collect()
+}
+
+fn command_desc(command: &Command, id_counter: &mut CommandId) -> CommandDesc {
+    let id = *id_counter;
+    *id_counter += 1;
+    let (brief, mut description) = brief_and_description(
+        command.get_about().map(|s| s.to_string()),
+        command.get_long_about().map(|s| s.to_string()),
+    );
+    if let Some(after) = command.get_after_long_help().or(command.get_after_help()) {
+        let description = description.get_or_insert_with(RichText::new);
+        for paragraph in paragraphs(&after.to_string()) {
+            description.add_lines(&paragraph);
+        }
+    }
+    let doc = CommandDoc {
+        brief,
+        description,
+        usage: usage(command),
+        arguments: command
+            .get_positionals()
+            .filter(|a| !is_hidden(a))
+            .map(argument_desc)
+            .collect(),
+        option_categories: option_categories(command),
+        groups: groups(command),
+    };
+    CommandDesc {
+        id,
+        name: command.get_name().to_string(),
+        aliases: command
+            .get_visible_aliases()
+            .map(|a| a.to_string())
+            .collect(),
+        doc,
+        error: None,
+        commands: command
+            .get_subcommands()
+            // The generated help command is not documented, as by the runner
+            .filter(|c| !c.is_hide_set() && c.get_name() != "help")
+            .map(|c| command_desc(c, id_counter))
+            .collect(),
+    }
+}
+
+/// Builds the documentation model directly from the definition of a clap program
+pub fn program_from_clap(command: &Command) -> ProgramDesc {
+    let mut command = command.clone();
+    // Propagates global arguments and adds generated arguments (e.g. --help) to all commands
+    command.build();
+    let version = command
+        .get_long_version()
+        .or(command.get_version())
+        .map(|v| format!("{} {v}", command.get_name()))
+        .unwrap_or_default();
+    ProgramDesc {
+        command: command_desc(&command, &mut 0),
+        version,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::builder::PossibleValue as ClapPossibleValue;
+    use clap::{ArgAction, ArgGroup, arg};
+    use std::path::PathBuf;
+
+    fn make_command() -> Command {
+        Command::new("prog")
+            .version("1.2.3")
+            .about("Fake program")
+            .arg(arg!(-v --verbose "Verbose output").action(ArgAction::SetTrue))
+            .arg(
+                arg!(-c --color <WHEN> "Coloring")
+                    .value_parser([
+                        ClapPossibleValue::new("auto"),
+                        ClapPossibleValue::new("always").help("Even when not a terminal"),
+                        ClapPossibleValue::new("never"),
+                    ])
+                    .default_value("auto")
+                    .visible_alias("colour"),
+            )
+            .arg(arg!(--secret "Hidden option").hide(true))
+            .subcommand(
+                Command::new("run")
+                    .about("Run something")
+                    .long_about("Run something\n\nThe file is executed\nin a sandbox.")
+                    .visible_alias("r")
+                    .arg(arg!(<FILE> "File to run"))
+                    .arg(
+                        arg!(-j --jobs <N> "Number of jobs")
+                            .env("PROG_JOBS")
+                            .help_heading("Execution"),
+                    ),
+            )
+            .subcommand(Command::new("internal").hide(true))
+    }
+
+    #[test]
+    fn test_program_from_clap() {
+        let program = program_from_clap(&make_command());
+        assert_eq!(program.version, "prog 1.2.3");
+        let root = &program.command;
+        assert_eq!(root.name, "prog");
+        assert_eq!(root.doc.brief, RichText::from_single_line("Fake program"));
+        assert_eq!(root.doc.usage[0].to_text(), "prog [OPTIONS] [COMMAND]");
+        let options = &root.doc.option_categories[0].options;
+        let names: Vec<_> = options.iter().map(|o| o.long.as_str()).collect();
+        assert_eq!(
+            names,
+            ["--verbose", "--color <WHEN>", "--help", "--version"]
+        );
+        assert_eq!(options[0].short.as_deref(), Some("-v"));
+        assert!(options[0].info.is_empty());
+        assert_eq!(
+            options[1].info,
+            ValueInfo {
+                value_name: Some("WHEN".to_string()),
+                default: Some("auto".to_string()),
+                possible_values: vec![
+                    PossibleValue::new("auto"),
+                    PossibleValue {
+                        name: "always".to_string(),
+                        help: Some("Even when not a terminal".to_string()),
+                    },
+                    PossibleValue::new("never"),
+                ],
+                env: None,
+                aliases: vec!["--colour".to_string()],
+                value_hint: None,
+            }
+        );
+
+        let commands: Vec<_> = root.commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(commands, ["run"]);
+        let run = &root.commands[0];
+        assert_eq!(run.id, 1);
+        assert_eq!(run.aliases, ["r"]);
+        assert_eq!(run.doc.brief, RichText::from_single_line("Run something"));
+        assert_eq!(
+            run.doc.description,
+            Some(RichText::from_single_line(
+                "The file is executed in a sandbox."
+            ))
+        );
+        assert_eq!(run.doc.usage[0].to_text(), "prog run [OPTIONS] <FILE>");
+        assert_eq!(run.doc.arguments[0].name, "<FILE>");
+        assert_eq!(
+            run.doc.arguments[0].info.value_name.as_deref(),
+            Some("FILE")
+        );
+        let titles: Vec<_> = run
+            .doc
+            .option_categories
+            .iter()
+            .map(|c| c.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Options", "Execution"]);
+        let jobs = &run.doc.option_categories[1].options[0];
+        assert_eq!(jobs.long, "--jobs <N>");
+        assert_eq!(jobs.info.env.as_deref(), Some("PROG_JOBS"));
+    }
+
+    #[test]
+    fn test_value_syntax() {
+        let command = Command::new("prog")
+            .arg(
+                arg!(--color [WHEN] "Coloring")
+                    .require_equals(true)
+                    .num_args(0..=1),
+            )
+            .arg(arg!(--config <FILE> "Config").value_parser(clap::value_parser!(PathBuf)))
+            .arg(arg!(--url <URL> "Server").value_hint(ValueHint::Url));
+        let program = program_from_clap(&command);
+        let options = &program.command.doc.option_categories[0].options;
+        assert_eq!(options[0].long, "--color[=<WHEN>]");
+        assert_eq!(options[0].info.value_hint, None);
+        assert_eq!(options[1].long, "--config <FILE>");
+        assert_eq!(options[1].info.value_hint.as_deref(), Some("path"));
+        assert_eq!(options[2].info.value_hint.as_deref(), Some("URL"));
+        assert!(
+            options[2]
+                .info
+                .details()
+                .contains(&("Value", "URL".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_program_from_clap_groups() {
+        let command = Command::new("prog")
+            .arg(arg!(--json "JSON output"))
+            .arg(arg!(--yaml "YAML output"))
+            .arg(arg!(--secret "Hidden").hide(true))
+            .arg(arg!([FILE] "Input"))
+            .group(
+                ArgGroup::new("format")
+                    .args(["json", "yaml", "secret"])
+                    .required(true),
+            )
+            .group(ArgGroup::new("input").args(["FILE", "json"]))
+            .group(ArgGroup::new("all").args(["json", "yaml"]).multiple(true));
+        let program = program_from_clap(&command);
+        let groups = &program.command.doc.groups;
+        assert_eq!(
+            groups,
+            &[
+                ArgGroupDesc {
+                    name: "format".to_string(),
+                    members: vec!["--json".to_string(), "--yaml".to_string()],
+                    required: true,
+                    multiple: false,
+                },
+                ArgGroupDesc {
+                    name: "input".to_string(),
+                    members: vec!["[FILE]".to_string(), "--json".to_string()],
+                    required: false,
+                    multiple: false,
+                },
+            ]
+        );
+        assert_eq!(groups[0].to_text(), "Exactly one of --json, --yaml");
+    }
+}
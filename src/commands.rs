@@ -1,42 +1,119 @@
 use crate::text::RichText;
 use askama::filters::{Escaper, Html};
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
 pub type CommandId = u32;
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+/// Structured metadata of an option or an argument, usually extracted from
+/// annotations like "[default: auto]" or "[env: FOO=]"
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ValueInfo {
+    pub value_name: Option<String>,
+    pub default: Option<String>,
+    pub possible_values: Vec<PossibleValue>,
+    pub env: Option<String>,
+    pub aliases: Vec<String>,
+    /// Kind of the value, e.g. "file path" or "URL"; only known for `clap::Command`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value_hint: Option<String>,
+}
+
+/// A value accepted by an option or an argument, with its help if the program shows it
+/// (e.g. in the long help of clap)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PossibleValue {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+}
+
+impl PossibleValue {
+    pub fn new(name: impl Into<String>) -> Self {
+        PossibleValue {
+            name: name.into(),
+            help: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct OptionDesc {
     pub short: Option<String>,
     pub long: String,
     pub brief: RichText,
     pub description: Option<RichText>,
+    #[serde(default)]
+    pub info: ValueInfo,
 }
 
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq, Eq))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ArgumentDesc {
     pub name: String,
     pub brief: RichText,
     pub description: Option<RichText>,
+    #[serde(default)]
+    pub info: ValueInfo,
+}
+
+impl ValueInfo {
+    pub fn is_empty(&self) -> bool {
+        self.details().is_empty() && self.described_values().is_empty()
+    }
+
+    /// Labeled values for renderers; the value name is omitted as it is already a part
+    /// of the option name and possible values with help are in `described_values`
+    pub fn details(&self) -> Vec<(&'static str, String)> {
+        let mut out = Vec::new();
+        if let Some(default) = &self.default {
+            out.push(("Default", default.clone()));
+        }
+        if self.described_values().is_empty() && !self.possible_values.is_empty() {
+            let names: Vec<_> = self
+                .possible_values
+                .iter()
+                .map(|v| v.name.as_str())
+                .collect();
+            out.push(("Possible values", names.join(", ")));
+        }
+        if let Some(env) = &self.env {
+            out.push(("Environment variable", env.clone()));
+        }
+        if !self.aliases.is_empty() {
+            out.push(("Aliases", self.aliases.join(", ")));
+        }
+        if let Some(value_hint) = &self.value_hint {
+            out.push(("Value", value_hint.clone()));
+        }
+        out
+    }
+
+    /// Possible values when at least one of them has help; renderers list them with
+    /// their help instead of the "Possible values" detail
+    pub fn described_values(&self) -> &[PossibleValue] {
+        if self.possible_values.iter().any(|v| v.help.is_some()) {
+            &self.possible_values
+        } else {
+            &[]
+        }
+    }
 }
 
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq, Eq))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CategoryDesc {
     pub title: String,
     pub options: Vec<OptionDesc>,
 }
 
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq, Eq))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum UsagePart {
     Command(String),
     Argument(String),
     Option(String),
 }
 
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq, Eq))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Usage {
     pub parts: Vec<UsagePart>,
 }
@@ -69,36 +146,93 @@ impl Usage {
         }
         out
     }
+
+    pub fn to_text(&self) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                UsagePart::Command(s) | UsagePart::Argument(s) | UsagePart::Option(s) => s.as_str(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Options and arguments that constrain each other, e.g. a required or mutually exclusive
+/// `ArgGroup`; only known for `clap::Command`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArgGroupDesc {
+    pub name: String,
+    /// Options and arguments as shown in the documentation, e.g. "--json" or "<FILE>"
+    pub members: Vec<String>,
+    /// At least one of the members has to be given
+    pub required: bool,
+    /// More members can be given together; otherwise they are mutually exclusive
+    pub multiple: bool,
+}
+
+impl ArgGroupDesc {
+    /// E.g. "Exactly one of" for a required group of mutually exclusive members
+    pub fn constraint(&self) -> &'static str {
+        match (self.required, self.multiple) {
+            (true, false) => "Exactly one of",
+            (true, true) => "At least one of",
+            (false, false) => "At most one of",
+            (false, true) => "Any of",
+        }
+    }
+
+    /// E.g. "Exactly one of --json, --yaml"
+    pub fn to_text(&self) -> String {
+        format!("{} {}", self.constraint(), self.members.join(", "))
+    }
 }
 
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq, Eq))]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CommandDoc {
     pub brief: RichText,
     pub description: Option<RichText>,
     pub usage: Vec<Usage>,
     pub arguments: Vec<ArgumentDesc>,
     pub option_categories: Vec<CategoryDesc>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<ArgGroupDesc>,
 }
 
 impl CommandDoc {
     pub fn is_args_effectively_empty(&self) -> bool {
-        self.arguments.iter().all(|arg| arg.brief.is_empty())
+        self.arguments
+            .iter()
+            .all(|arg| arg.brief.is_empty() && arg.info.is_empty())
     }
 }
 
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq, Eq))]
+#[derive(Debug, PartialEq, Eq)]
 pub struct CommandOuterDoc {
     pub name: String,
+    pub aliases: Vec<String>,
+    /// One-line description from the list of subcommands of the parent command
+    pub brief: RichText,
+}
+
+/// Failure of running or parsing help of a command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractionError {
+    pub message: String,
+    pub exit_code: Option<i32>,
+    pub stderr: Option<String>,
 }
 
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq, Eq))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CommandDesc {
     pub id: CommandId,
     pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
     pub doc: CommandDoc,
+    /// Set when the documentation of the command could not be extracted (`--keep-going`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ExtractionError>,
     pub commands: Vec<CommandDesc>,
 }
 
@@ -126,8 +260,81 @@ impl CommandDesc {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProgramDesc {
     pub command: CommandDesc,
     pub version: String,
 }
+
+/// Models shared by tests of the renderers, the diff and the config
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::*;
+    use crate::extractor::clap_parser::ClapParser;
+    use crate::extractor::parser::HelpParser;
+    use crate::extractor::sections::parse_sections;
+    use crate::extractor::value_info::extract_value_info;
+
+    pub(crate) fn make_option(long: &str, brief: &str) -> OptionDesc {
+        OptionDesc {
+            short: None,
+            long: long.to_string(),
+            brief: RichText::from_single_line(brief),
+            description: None,
+            info: ValueInfo::default(),
+        }
+    }
+
+    /// Command with the brief "Brief of <name>"; options are in a single category
+    pub(crate) fn make_command(
+        name: &str,
+        options: Vec<OptionDesc>,
+        commands: Vec<CommandDesc>,
+    ) -> CommandDesc {
+        let option_categories = if options.is_empty() {
+            Vec::new()
+        } else {
+            vec![CategoryDesc {
+                title: "Options".to_string(),
+                options,
+            }]
+        };
+        CommandDesc {
+            id: 0,
+            name: name.to_string(),
+            aliases: Vec::new(),
+            doc: CommandDoc {
+                brief: RichText::from_single_line(&format!("Brief of {name}")),
+                option_categories,
+                ..CommandDoc::default()
+            },
+            error: None,
+            commands,
+        }
+    }
+
+    /// Program of version "prog 1.0"; ids of commands are assigned in depth-first order
+    pub(crate) fn make_program(command: CommandDesc) -> ProgramDesc {
+        fn assign_ids(command: &mut CommandDesc, next_id: &mut CommandId) {
+            command.id = *next_id;
+            *next_id += 1;
+            for child in &mut command.commands {
+                assign_ids(child, next_id);
+            }
+        }
+        let mut command = command;
+        assign_ids(&mut command, &mut 0);
+        ProgramDesc {
+            command,
+            version: "prog 1.0".to_string(),
+        }
+    }
+
+    /// Documentation of a command parsed from clap help, as done by the runner
+    pub(crate) fn parse_clap(text: &str) -> CommandDoc {
+        let mut sections = parse_sections(text);
+        let mut doc = ClapParser::new().parse(&mut sections).unwrap().0;
+        extract_value_info(&mut doc);
+        doc
+    }
+}
@@ -0,0 +1,382 @@
+use crate::builder::{Backend, DocBuilder, DocPage, Document, RenderOptions, render_with_options};
+use crate::commands::{CommandDesc, ProgramDesc};
+use crate::extractor::environment::ProgramEnvironment;
+use crate::extractor::invocation::{HelpInvocation, OutputStream, VersionSource};
+use crate::extractor::parser::BuiltinParser;
+use crate::text::RichText;
+use anyhow::{Context, anyhow, bail};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Configuration of the documented program and outputs, usually stored in `cli_doc.toml`
+/// next to the sources of the program
+///
+/// Relative paths in the file are relative to the directory of the file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectConfig {
+    pub program: ProgramConfig,
+    pub environment: EnvironmentConfig,
+    pub filter: FilterConfig,
+    /// Generated documentation; a single HTML page when empty
+    #[serde(rename = "output")]
+    pub outputs: Vec<OutputConfig>,
+    /// Documentation of commands replaced in the extracted model; keys are paths
+    /// of commands without the program (e.g. "remote add", "" for the program itself)
+    pub overrides: BTreeMap<String, CommandOverride>,
+    #[serde(skip)]
+    base_dir: PathBuf,
+}
+
+/// The command line of the program and the way its help is obtained
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProgramConfig {
+    /// The program followed by its fixed arguments, e.g. `["python", "-m", "tool"]`
+    pub command: Vec<String>,
+    pub name: Option<String>,
+    pub parser: Option<BuiltinParser>,
+    /// 0 means the number of CPUs
+    pub jobs: usize,
+    /// Seconds; 0 means no limit
+    pub timeout: u64,
+    pub keep_going: bool,
+    pub help_flag: Option<String>,
+    pub help_command: Option<String>,
+    pub version_flag: Option<String>,
+    pub version_string: Option<String>,
+    pub help_output: OutputStream,
+    pub accept_failure: bool,
+}
+
+impl Default for ProgramConfig {
+    fn default() -> Self {
+        ProgramConfig {
+            command: Vec::new(),
+            name: None,
+            parser: None,
+            jobs: 0,
+            timeout: 30,
+            keep_going: false,
+            help_flag: None,
+            help_command: None,
+            version_flag: None,
+            version_string: None,
+            help_output: OutputStream::default(),
+            accept_failure: false,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnvironmentConfig {
+    pub clear: bool,
+    pub unset: Vec<String>,
+    pub vars: BTreeMap<String, String>,
+    pub columns: Option<usize>,
+    pub no_color: bool,
+    pub c_locale: bool,
+    pub working_dir: Option<PathBuf>,
+}
+
+/// Selection of documented subcommands, see `RunnerConfig`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FilterConfig {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    pub max_depth: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutputConfig {
+    /// "html", "json", "markdown", "markdown-pages" or "man-pages"
+    pub format: Backend,
+    /// Output file (or directory); the default file name of the format when not set
+    pub path: Option<PathBuf>,
+    /// CSS file appended to the style of the HTML page
+    pub theme: Option<PathBuf>,
+    /// Files added to the outputs with multiple pages, e.g. an introduction
+    #[serde(default)]
+    pub extra_pages: Vec<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CommandOverride {
+    pub brief: Option<String>,
+    /// Paragraphs are separated by empty lines
+    pub description: Option<String>,
+}
+
+/// Lines of paragraphs separated by empty lines as rich text
+fn parse_text(text: &str) -> RichText {
+    let mut out = RichText::new();
+    for paragraph in text.split("\n\n") {
+        let lines: Vec<_> = paragraph
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .collect();
+        out.add_lines(&lines);
+    }
+    out
+}
+
+fn find_command<'a>(command: &'a mut CommandDesc, path: &str) -> Option<&'a mut CommandDesc> {
+    let mut command = command;
+    for name in path.split_whitespace() {
+        command = command.commands.iter_mut().find(|c| c.name == name)?;
+    }
+    Some(command)
+}
+
+impl ProjectConfig {
+    pub const FILENAME: &'static str = "cli_doc.toml";
+
+    pub fn parse(input: &str, base_dir: &Path) -> anyhow::Result<Self> {
+        let mut config: ProjectConfig = toml::from_str(input)?;
+        config.base_dir = base_dir.to_path_buf();
+        Ok(config)
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let input = std::fs::read_to_string(path)
+            .with_context(|| format!("Cannot read config {}", path.display()))?;
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+        Self::parse(&input, base_dir).with_context(|| format!("Invalid config {}", path.display()))
+    }
+
+    /// Loads `cli_doc.toml` from the current directory when it exists
+    pub fn discover() -> anyhow::Result<Option<Self>> {
+        let path = Path::new(Self::FILENAME);
+        if path.exists() {
+            Self::load(path).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn resolve(&self, path: &Path) -> PathBuf {
+        self.base_dir.join(path)
+    }
+
+    /// Builder configured by the program, environment and filter sections
+    pub fn builder(&self) -> anyhow::Result<DocBuilder> {
+        let program = &self.program;
+        let Some((executable, program_args)) = program.command.split_first() else {
+            bail!("The program is not set (`command` in section `program`)");
+        };
+        // A bare name is looked up in PATH, as in a shell
+        let executable = if executable.contains(std::path::MAIN_SEPARATOR) {
+            self.resolve(Path::new(executable))
+        } else {
+            PathBuf::from(executable)
+        };
+        let environment = &self.environment;
+        let mut builder = DocBuilder::new(executable)
+            .program_args(program_args)
+            .jobs(program.jobs)
+            .timeout((program.timeout > 0).then(|| Duration::from_secs(program.timeout)))
+            .keep_going(program.keep_going)
+            .help(match (&program.help_command, &program.help_flag) {
+                (Some(_), Some(_)) => bail!("`help_command` and `help_flag` cannot be both set"),
+                (Some(name), None) => HelpInvocation::Command(name.clone()),
+                (None, Some(flag)) => HelpInvocation::Flag(flag.clone()),
+                (None, None) => HelpInvocation::default(),
+            })
+            .version(match (&program.version_string, &program.version_flag) {
+                (Some(_), Some(_)) => {
+                    bail!("`version_string` and `version_flag` cannot be both set")
+                }
+                (Some(version), None) => VersionSource::Fixed(version.clone()),
+                (None, Some(flag)) => VersionSource::Flag(flag.clone()),
+                (None, None) => VersionSource::default(),
+            })
+            .output_stream(program.help_output)
+            .accept_failure(program.accept_failure)
+            .environment(ProgramEnvironment {
+                clear: environment.clear,
+                remove: environment.unset.clone(),
+                vars: environment.vars.clone().into_iter().collect(),
+                columns: environment.columns,
+                no_color: environment.no_color,
+                c_locale: environment.c_locale,
+                working_dir: environment.working_dir.as_deref().map(|d| self.resolve(d)),
+            })
+            .max_depth(self.filter.max_depth);
+        if let Some(name) = &program.name {
+            builder = builder.name(name);
+        }
+        if let Some(parser) = program.parser {
+            builder = builder.parser(parser);
+        }
+        for pattern in &self.filter.include {
+            builder = builder.include(pattern);
+        }
+        for pattern in &self.filter.exclude {
+            builder = builder.exclude(pattern);
+        }
+        Ok(builder)
+    }
+
+    /// Replaces the documentation of commands by the overrides
+    pub fn apply_overrides(&self, program: &mut ProgramDesc) -> anyhow::Result<()> {
+        for (path, command_override) in &self.overrides {
+            let command = find_command(&mut program.command, path)
+                .ok_or_else(|| anyhow!("Override of unknown command `{path}`"))?;
+            if let Some(brief) = &command_override.brief {
+                command.doc.brief = parse_text(brief);
+            }
+            if let Some(description) = &command_override.description {
+                command.doc.description = Some(parse_text(description));
+            }
+        }
+        Ok(())
+    }
+
+    /// Configured outputs, or a single HTML page when none is configured; default paths
+    /// of outputs are relative to the directory of the file too
+    pub fn outputs(&self) -> Vec<OutputConfig> {
+        let default = [OutputConfig {
+            format: Backend::Html,
+            path: None,
+            theme: None,
+            extra_pages: Vec::new(),
+        }];
+        let outputs = if self.outputs.is_empty() {
+            &default[..]
+        } else {
+            &self.outputs
+        };
+        outputs
+            .iter()
+            .map(|output| OutputConfig {
+                format: output.format,
+                path: Some(self.resolve(&output.path())),
+                theme: output.theme.as_deref().map(|p| self.resolve(p)),
+                extra_pages: output.extra_pages.iter().map(|p| self.resolve(p)).collect(),
+            })
+            .collect()
+    }
+}
+
+impl OutputConfig {
+    pub fn path(&self) -> PathBuf {
+        self.path
+            .clone()
+            .unwrap_or_else(|| self.format.default_filename().into())
+    }
+
+    /// Renders the program with the theme and adds the extra pages
+    pub fn render(&self, program: &ProgramDesc) -> anyhow::Result<Document> {
+        let theme = match &self.theme {
+            Some(path) => Some(
+                std::fs::read_to_string(path)
+                    .with_context(|| format!("Cannot read theme {}", path.display()))?,
+            ),
+            None => None,
+        };
+        let mut document = render_with_options(program, self.format, &RenderOptions { theme })?;
+        if !self.extra_pages.is_empty() {
+            let Document::Pages(pages) = &mut document else {
+                bail!("Extra pages are supported only by formats with multiple pages");
+            };
+            for path in &self.extra_pages {
+                let filename = path
+                    .file_name()
+                    .ok_or_else(|| anyhow!("Invalid extra page {}", path.display()))?;
+                pages.push(DocPage {
+                    filename: filename.to_string_lossy().to_string(),
+                    content: std::fs::read_to_string(path)
+                        .with_context(|| format!("Cannot read extra page {}", path.display()))?,
+                });
+            }
+        }
+        Ok(document)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::fixtures::{make_command, make_program};
+
+    const CONFIG: &str = r#"
+[program]
+command = ["python", "-m", "tool"]
+name = "tool"
+help_flag = "-h"
+
+[environment]
+columns = 100
+vars = { PAGER = "cat" }
+
+[filter]
+exclude = ["debug *"]
+
+[[output]]
+format = "html"
+path = "docs/tool.html"
+
+[[output]]
+format = "man-pages"
+
+[overrides."remote add"]
+brief = "Add a remote"
+description = """
+First paragraph
+continues here.
+
+Second paragraph"""
+"#;
+
+    #[test]
+    fn test_parse_config() {
+        let config = ProjectConfig::parse(CONFIG, Path::new("project")).unwrap();
+        assert_eq!(config.program.command, ["python", "-m", "tool"]);
+        assert_eq!(config.program.timeout, 30);
+        assert_eq!(config.environment.columns, Some(100));
+        assert_eq!(config.filter.exclude, ["debug *"]);
+        let outputs = config.outputs();
+        assert_eq!(outputs[0].format, Backend::Html);
+        assert_eq!(outputs[0].path(), Path::new("project/docs/tool.html"));
+        assert_eq!(outputs[1].format, Backend::ManPages);
+        assert_eq!(outputs[1].path(), Path::new("project/man"));
+        assert!(config.builder().is_ok());
+
+        assert!(ProjectConfig::parse("[program]\nunknown = 1", Path::new("")).is_err());
+        let config = ProjectConfig::parse("", Path::new("project")).unwrap();
+        assert!(config.builder().is_err());
+        assert_eq!(config.outputs()[0].format, Backend::Html);
+        assert_eq!(config.outputs()[0].path(), Path::new("project/doc.html"));
+    }
+
+    #[test]
+    fn test_apply_overrides() {
+        let config = ProjectConfig::parse(CONFIG, Path::new("")).unwrap();
+        let mut program = make_program(make_command(
+            "tool",
+            vec![],
+            vec![make_command(
+                "remote",
+                vec![],
+                vec![make_command("add", vec![], vec![])],
+            )],
+        ));
+        config.apply_overrides(&mut program).unwrap();
+        let add = &program.command.commands[0].commands[0];
+        assert_eq!(add.doc.brief, RichText::from_single_line("Add a remote"));
+        assert_eq!(
+            add.doc.description.as_ref().unwrap().to_text(),
+            "First paragraph continues here.\nSecond paragraph"
+        );
+
+        program.command.commands.clear();
+        assert!(config.apply_overrides(&mut program).is_err());
+    }
+}
@@ -0,0 +1,401 @@
+use crate::commands::{
+    ArgGroupDesc, ArgumentDesc, CommandDesc, OptionDesc, ProgramDesc, Usage, ValueInfo,
+};
+use crate::text::RichText;
+use std::fmt;
+
+/// A single difference between two extracted models; `command` is the path of the command
+/// (e.g. "prog remote add") and `item` names the changed part (e.g. "option `--jobs <N>`")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelChange {
+    Added {
+        command: String,
+        item: String,
+    },
+    Removed {
+        command: String,
+        item: String,
+    },
+    Changed {
+        command: String,
+        item: String,
+        old: String,
+        new: String,
+    },
+}
+
+impl fmt::Display for ModelChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModelChange::Added { command, item } => write!(f, "{command}: added {item}"),
+            ModelChange::Removed { command, item } => write!(f, "{command}: removed {item}"),
+            ModelChange::Changed {
+                command,
+                item,
+                old,
+                new,
+            } => {
+                write!(f, "{command}: changed {item}")?;
+                // Unchanged lines are omitted
+                for (sign, line) in diff_lines(old, new) {
+                    if sign != ' ' {
+                        write!(f, "\n  {sign} {line}")?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Lines of both texts marked by ' ', '-' or '+', in the order of the texts; the unchanged
+/// lines are the longest common subsequence
+fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<(char, &'a str)> {
+    let old: Vec<_> = old.lines().collect();
+    let new: Vec<_> = new.lines().collect();
+    // common[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            out.push((' ', old[i]));
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && common[i + 1][j] >= common[i][j + 1]) {
+            out.push(('-', old[i]));
+            i += 1;
+        } else {
+            out.push(('+', new[j]));
+            j += 1;
+        }
+    }
+    out
+}
+
+fn optional_text(text: &Option<RichText>) -> String {
+    text.as_ref().map(RichText::to_text).unwrap_or_default()
+}
+
+fn info_text(info: &ValueInfo) -> impl Iterator<Item = String> {
+    let values = info
+        .described_values()
+        .iter()
+        .map(|value| match &value.help {
+            Some(help) => format!("[possible value {}: {help}]", value.name),
+            None => format!("[possible value {}]", value.name),
+        });
+    info.details()
+        .into_iter()
+        .map(|(label, value)| format!("[{label}: {value}]"))
+        .chain(values)
+}
+
+fn option_text(option: &OptionDesc) -> String {
+    let mut lines = vec![match &option.short {
+        Some(short) => format!("{short}, {}", option.long),
+        None => option.long.clone(),
+    }];
+    lines.push(option.brief.to_text());
+    lines.push(optional_text(&option.description));
+    lines.extend(info_text(&option.info));
+    lines.retain(|l| !l.is_empty());
+    lines.join("\n")
+}
+
+fn argument_text(argument: &ArgumentDesc) -> String {
+    let mut lines = vec![
+        argument.brief.to_text(),
+        optional_text(&argument.description),
+    ];
+    lines.extend(info_text(&argument.info));
+    lines.retain(|l| !l.is_empty());
+    lines.join("\n")
+}
+
+/// Compares items of two lists identified by a key; items present in both lists are
+/// compared by their text
+fn diff_items<T>(
+    path: &str,
+    kind: &str,
+    old: &[T],
+    new: &[T],
+    key: impl Fn(&T) -> &str,
+    text: impl Fn(&T) -> String,
+    changes: &mut Vec<ModelChange>,
+) {
+    for old_item in old {
+        let item = format!("{kind} `{}`", key(old_item));
+        match new.iter().find(|n| key(n) == key(old_item)) {
+            None => changes.push(ModelChange::Removed {
+                command: path.to_string(),
+                item,
+            }),
+            Some(new_item) => {
+                let (old, new) = (text(old_item), text(new_item));
+                if old != new {
+                    changes.push(ModelChange::Changed {
+                        command: path.to_string(),
+                        item,
+                        old,
+                        new,
+                    });
+                }
+            }
+        }
+    }
+    for new_item in new {
+        if !old.iter().any(|o| key(o) == key(new_item)) {
+            changes.push(ModelChange::Added {
+                command: path.to_string(),
+                item: format!("{kind} `{}`", key(new_item)),
+            });
+        }
+    }
+}
+
+/// Options paired with the title of their category, so moving an option is reported
+fn categorized_options(command: &CommandDesc) -> Vec<(&str, &OptionDesc)> {
+    command
+        .doc
+        .option_categories
+        .iter()
+        .flat_map(|c| c.options.iter().map(|o| (c.title.as_str(), o)))
+        .collect()
+}
+
+fn diff_commands(path: &str, old: &CommandDesc, new: &CommandDesc, changes: &mut Vec<ModelChange>) {
+    let mut diff_field = |item: &str, old: String, new: String| {
+        if old != new {
+            changes.push(ModelChange::Changed {
+                command: path.to_string(),
+                item: item.to_string(),
+                old,
+                new,
+            });
+        }
+    };
+    diff_field("aliases", old.aliases.join(", "), new.aliases.join(", "));
+    diff_field("brief", old.doc.brief.to_text(), new.doc.brief.to_text());
+    diff_field(
+        "description",
+        optional_text(&old.doc.description),
+        optional_text(&new.doc.description),
+    );
+    let usage = |command: &CommandDesc| {
+        let lines: Vec<_> = command.doc.usage.iter().map(Usage::to_text).collect();
+        lines.join("\n")
+    };
+    diff_field("usage", usage(old), usage(new));
+    let error = |command: &CommandDesc| {
+        command
+            .error
+            .as_ref()
+            .map(|e| e.message.clone())
+            .unwrap_or_default()
+    };
+    diff_field("error", error(old), error(new));
+
+    let categories = |command: &CommandDesc| {
+        let titles: Vec<_> = command
+            .doc
+            .option_categories
+            .iter()
+            .map(|c| c.title.as_str())
+            .collect();
+        titles.join("\n")
+    };
+    diff_field("option categories", categories(old), categories(new));
+
+    diff_items(
+        path,
+        "argument",
+        &old.doc.arguments,
+        &new.doc.arguments,
+        |a| a.name.as_str(),
+        argument_text,
+        changes,
+    );
+    diff_items(
+        path,
+        "option",
+        &categorized_options(old),
+        &categorized_options(new),
+        |(_, o)| o.long.as_str(),
+        |(category, o)| format!("{}\n[Category: {category}]", option_text(o)),
+        changes,
+    );
+    diff_items(
+        path,
+        "group",
+        &old.doc.groups,
+        &new.doc.groups,
+        |g| g.name.as_str(),
+        ArgGroupDesc::to_text,
+        changes,
+    );
+    diff_items(
+        path,
+        "command",
+        &old.commands,
+        &new.commands,
+        |c| c.name.as_str(),
+        |_| String::new(),
+        changes,
+    );
+    for old_command in &old.commands {
+        if let Some(new_command) = new.commands.iter().find(|c| c.name == old_command.name) {
+            let path = format!("{path} {}", new_command.name);
+            diff_commands(&path, old_command, new_command, changes);
+        }
+    }
+}
+
+/// Lists differences of the documentation of two models
+///
+/// Options repeated from parent commands are compared only in the parent, so a change of
+/// a global option is reported once.
+pub fn diff_programs(old: &ProgramDesc, new: &ProgramDesc) -> Vec<ModelChange> {
+    let mut old = old.clone();
+    let mut new = new.clone();
+    old.command.prune_repeated_options();
+    new.command.prune_repeated_options();
+    let mut changes = Vec::new();
+    let path = new.command.name.clone();
+    if old.version != new.version {
+        changes.push(ModelChange::Changed {
+            command: path.clone(),
+            item: "version".to_string(),
+            old: old.version,
+            new: new.version,
+        });
+    }
+    diff_commands(&path, &old.command, &new.command, &mut changes);
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CategoryDesc;
+    use crate::commands::fixtures::{make_command, make_option};
+
+    #[test]
+    fn test_diff_programs() {
+        let help = make_option("--help", "Print help");
+        let old = ProgramDesc {
+            command: make_command(
+                "prog",
+                vec![help.clone(), make_option("--color <WHEN>", "Coloring")],
+                vec![
+                    make_command("run", vec![help.clone()], vec![]),
+                    make_command("old", vec![help.clone()], vec![]),
+                ],
+            ),
+            version: "prog 1.0".to_string(),
+        };
+        assert!(diff_programs(&old, &old).is_empty());
+
+        let mut run = make_command("run", vec![help.clone()], vec![]);
+        run.doc.brief = RichText::from_single_line("Run a file");
+        run.doc.option_categories[0]
+            .options
+            .push(make_option("--jobs <N>", "Number of jobs"));
+        let new = ProgramDesc {
+            command: make_command(
+                "prog",
+                vec![help.clone(), make_option("--color <WHEN>", "Colors")],
+                vec![run, make_command("new", vec![help], vec![])],
+            ),
+            version: "prog 1.1".to_string(),
+        };
+        let mut new = new;
+        new.command.doc.groups.push(ArgGroupDesc {
+            name: "output".to_string(),
+            members: vec!["--color <WHEN>".to_string()],
+            required: true,
+            multiple: false,
+        });
+        let changes: Vec<_> = diff_programs(&old, &new)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                "prog: changed version\n  - prog 1.0\n  + prog 1.1",
+                "prog: changed option `--color <WHEN>`\n  - Coloring\n  + Colors",
+                "prog: added group `output`",
+                "prog: removed command `old`",
+                "prog: added command `new`",
+                "prog run: changed brief\n  - Brief of run\n  + Run a file",
+                // `--help` is pruned as it is repeated from the parent
+                "prog run: changed option categories\n  + Options",
+                "prog run: added option `--jobs <N>`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_option_categories() {
+        let old = ProgramDesc {
+            command: make_command(
+                "prog",
+                vec![
+                    make_option("--help", "Print help"),
+                    make_option("--jobs <N>", "Number of jobs"),
+                ],
+                vec![],
+            ),
+            version: "prog 1.0".to_string(),
+        };
+        let mut new = old.clone();
+        let categories = &mut new.command.doc.option_categories;
+        categories[0].title = "General".to_string();
+        let jobs = categories[0].options.pop().unwrap();
+        categories.push(CategoryDesc {
+            title: "Performance".to_string(),
+            options: vec![jobs],
+        });
+        let changes: Vec<_> = diff_programs(&old, &new)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                "prog: changed option categories\n  - Options\n  + General\n  + Performance",
+                "prog: changed option `--help`\n  - [Category: Options]\n  + [Category: General]",
+                "prog: changed option `--jobs <N>`\n  - [Category: Options]\n  + [Category: Performance]",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        assert_eq!(
+            diff_lines("a\nb\nc", "a\nc\nb"),
+            [(' ', "a"), ('-', "b"), (' ', "c"), ('+', "b")]
+        );
+        // Repeated and moved lines are reported, unlike with a comparison of sets of lines
+        let change = ModelChange::Changed {
+            command: "prog".to_string(),
+            item: "description".to_string(),
+            old: "Usage\n\nNotes".to_string(),
+            new: "Notes\n\nUsage\n\nNotes".to_string(),
+        };
+        assert_eq!(
+            change.to_string(),
+            "prog: changed description\n  + Notes\n  + "
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+const ESC: char = '\x1b';
+const BEL: char = '\x07';
+/// Single-character form of `ESC [`
+const CSI: char = '\u{9b}';
+const BACKSPACE: char = '\x08';
+
+/// Skips parameters and the final character of a control sequence (`ESC [ ... m`)
+fn skip_csi(chars: &mut Peekable<Chars>) {
+    for c in chars.by_ref() {
+        if ('@'..='~').contains(&c) {
+            break;
+        }
+    }
+}
+
+/// Skips a string terminated by BEL or `ESC \` (e.g. OSC 8 hyperlinks)
+fn skip_string(chars: &mut Peekable<Chars>) {
+    while let Some(c) = chars.next() {
+        if c == BEL {
+            break;
+        }
+        if c == ESC {
+            chars.next_if_eq(&'\\');
+            break;
+        }
+    }
+}
+
+/// Removes terminal escape sequences (colors, hyperlinks, ...) and backspace
+/// overstriking (bold and underline of `man`) from the output of a program
+pub(crate) fn strip_ansi(input: &str) -> String {
+    if !input.contains([ESC, CSI, BACKSPACE]) {
+        return input.to_string();
+    }
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            ESC => match chars.next() {
+                Some('[') => skip_csi(&mut chars),
+                Some(']' | 'P' | 'X' | '^' | '_') => skip_string(&mut chars),
+                // Intermediate characters are followed by one final character, e.g. `ESC ( B`
+                Some(' '..='/') => {
+                    while chars.next_if(|c| (' '..='/').contains(c)).is_some() {}
+                    chars.next();
+                }
+                _ => {}
+            },
+            CSI => skip_csi(&mut chars),
+            // "_\bx" is underlined and "x\bx" is bold "x"
+            BACKSPACE => {
+                out.pop();
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(strip_ansi("plain text"), "plain text");
+        assert_eq!(
+            strip_ansi("\x1b[1m\x1b[4mUsage:\x1b[0m \x1b[1mprog\x1b[0m [OPTIONS]"),
+            "Usage: prog [OPTIONS]"
+        );
+        assert_eq!(strip_ansi("\x1b[38;5;208mcolor\x1b[m"), "color");
+        assert_eq!(
+            strip_ansi("See \x1b]8;;https://example.com\x1b\\docs\x1b]8;;\x07 here"),
+            "See docs here"
+        );
+        assert_eq!(strip_ansi("\x1b(Bcharset\x1b="), "charset");
+        assert_eq!(strip_ansi("\u{9b}31mred\u{9b}0m"), "red");
+        assert_eq!(
+            strip_ansi("N\x08NA\x08AM\x08ME\x08E _\x08f_\x08i"),
+            "NAME fi"
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use crate::commands::{
+    ArgumentDesc, CategoryDesc, CommandDoc, CommandOuterDoc, OptionDesc, ValueInfo,
+};
+use crate::extractor::parser::HelpParser;
+use crate::extractor::sections::Section;
+use crate::extractor::usage::parse_grouped_usage;
+use crate::extractor::usage::{group_title, split_brief, split_short_long};
+use crate::text::RichText;
+use anyhow::bail;
+
+/// Parser of help generated by Python `argparse`
+pub(crate) struct ArgparseParser {}
+
+/// A single item of an argument group, e.g. "-f FOO, --foo FOO  help text"
+struct Entry<'a> {
+    names: &'a str,
+    help: Vec<&'a str>,
+    /// Items nested under a subparsers entry, e.g. "{build,run}"
+    choices: Vec<Entry<'a>>,
+}
+
+impl ArgparseParser {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+impl HelpParser for ArgparseParser {
+    fn name(&self) -> &'static str {
+        "argparse"
+    }
+
+    fn detect(&self, sections: &[Section]) -> bool {
+        sections
+            .first()
+            .is_some_and(|s| s.first_line().starts_with("usage: "))
+    }
+
+    fn parse(
+        &self,
+        sections: &mut [Section],
+    ) -> anyhow::Result<(CommandDoc, Vec<CommandOuterDoc>)> {
+        let Some(first) = sections.first() else {
+            bail!("Input is empty");
+        };
+        let Some(usage_start) = first.first_line().strip_prefix("usage: ") else {
+            bail!("Usage section not found");
+        };
+        let mut usage = usage_start.trim().to_string();
+        for line in first.lines()[1..]
+            .iter()
+            .copied()
+            .chain(first.flatten_child_lines())
+        {
+            usage.push(' ');
+            usage.push_str(line.trim());
+        }
+
+        let mut text = RichText::new();
+        let mut arguments = Vec::new();
+        let mut option_categories = Vec::new();
+        let mut commands = Vec::new();
+        for section in &sections[1..] {
+            let Some(title) = group_title(section) else {
+                section.as_rich_text_into(&mut text);
+                continue;
+            };
+            let mut options = Vec::new();
+            for entry in parse_entries(section) {
+                if entry.names.starts_with('-') {
+                    let names: Vec<&str> = entry.names.split(", ").collect();
+                    let (short, long) = split_short_long(&names);
+                    options.push(OptionDesc {
+                        short,
+                        long,
+                        brief: entry.help_as_rich_text(),
+                        description: None,
+                        info: ValueInfo::default(),
+                    });
+                } else if entry.names.starts_with('{')
+                    && usage.contains(&format!("{} ...", entry.names))
+                {
+                    commands.extend(entry.commands());
+                } else {
+                    arguments.push(ArgumentDesc {
+                        name: entry.names.to_string(),
+                        brief: entry.help_as_rich_text(),
+                        description: None,
+                        info: ValueInfo::default(),
+                    });
+                }
+            }
+            if !options.is_empty() {
+                option_categories.push(CategoryDesc {
+                    title: capitalize(title),
+                    options,
+                });
+            }
+        }
+
+        // argparse has no dedicated brief, the first paragraph of the description is used
+        let (brief, description) = split_brief(text);
+
+        Ok((
+            CommandDoc {
+                brief,
+                description,
+                usage: vec![parse_grouped_usage(&usage)],
+                arguments,
+                option_categories,
+                groups: Vec::new(),
+            },
+            commands,
+        ))
+    }
+}
+
+impl<'a> Entry<'a> {
+    fn new(line: &'a str) -> Self {
+        let (names, help) = split_help(line);
+        Entry {
+            names,
+            help: help.map(|(_, help)| vec![help]).unwrap_or_default(),
+            choices: Vec::new(),
+        }
+    }
+
+    fn help_as_rich_text(&self) -> RichText {
+        let mut text = RichText::new();
+        if !self.help.is_empty() {
+            text.add_lines(&[self.help.join(" ").as_str()]);
+        }
+        text
+    }
+
+    fn commands(&self) -> Vec<CommandOuterDoc> {
+        if self.choices.is_empty() {
+            return self
+                .names
+                .trim_start_matches('{')
+                .trim_end_matches('}')
+                .split(',')
+                .map(|s| CommandOuterDoc {
+                    name: s.trim().to_string(),
+                    aliases: Vec::new(),
+                    brief: RichText::new(),
+                })
+                .collect();
+        }
+        self.choices
+            .iter()
+            .map(|c| {
+                // Aliases are listed in parentheses, e.g. "build (b, bld)"
+                let (name, aliases) = match c.names.split_once(" (") {
+                    Some((name, aliases)) => (
+                        name,
+                        aliases
+                            .trim_end_matches(')')
+                            .split(", ")
+                            .map(|s| s.to_string())
+                            .collect(),
+                    ),
+                    None => (c.names, Vec::new()),
+                };
+                CommandOuterDoc {
+                    name: name.to_string(),
+                    aliases,
+                    brief: c.help_as_rich_text(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Splits "-f FOO, --foo FOO   help" into names and the help text with its offset
+fn split_help(line: &str) -> (&str, Option<(usize, &str)>) {
+    if let Some(pos) = line.find("  ") {
+        let help = line[pos..].trim_start();
+        (&line[..pos], Some((line.len() - help.len(), help)))
+    } else {
+        (line.trim_end(), None)
+    }
+}
+
+fn parse_entries<'a>(section: &Section<'a>) -> Vec<Entry<'a>> {
+    let lines = section.child_lines_with_indent();
+    let Some(base) = lines.iter().map(|(indent, _)| *indent).min() else {
+        return Vec::new();
+    };
+    // Column where help texts start; continuation lines of the help are aligned to it
+    let help_column = lines
+        .iter()
+        .filter_map(|(indent, line)| split_help(line).1.map(|(offset, _)| indent + offset))
+        .min();
+
+    let mut entries: Vec<Entry> = Vec::new();
+    for (indent, line) in lines {
+        let is_continuation = match help_column {
+            Some(column) => indent >= column,
+            None => indent > base,
+        };
+        if let Some(entry) = entries.last_mut().filter(|_| indent > base) {
+            if is_continuation {
+                if let Some(choice) = entry.choices.last_mut() {
+                    choice.help.push(line);
+                } else {
+                    entry.help.push(line);
+                }
+            } else {
+                entry.choices.push(Entry::new(line));
+            }
+        } else {
+            entries.push(Entry::new(line));
+        }
+    }
+    entries
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .map(|c| c.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
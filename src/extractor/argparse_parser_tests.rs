@@ -0,0 +1,562 @@
+use crate::commands::{CommandDoc, CommandOuterDoc};
+use crate::extractor::argparse_parser::ArgparseParser;
+use crate::extractor::parser::HelpParser;
+use crate::extractor::sections::parse_sections;
+use insta::assert_debug_snapshot;
+
+fn parse_argparse(text: &str) -> (CommandDoc, Vec<CommandOuterDoc>) {
+    let mut sections = parse_sections(text);
+    ArgparseParser::new().parse(&mut sections).unwrap()
+}
+
+#[test]
+fn test_parse_argparse_main() {
+    let text = "usage: tool [-h] [-v] [--output-directory-for-generated-files DIR]
+            [-f {json,yaml}] [--timeout TIMEOUT]
+            {build,run} ...
+
+Process some files. This tool does a lot of useful things with files.
+
+positional arguments:
+  {build,run}           available commands
+    build               build the project
+    run                 run the project
+
+options:
+  -h, --help            show this help message and exit
+  -v, --verbose         increase output verbosity
+  --output-directory-for-generated-files DIR
+                        directory where generated files are written; it is
+                        created when it does not exist
+  -f {json,yaml}, --format {json,yaml}
+                        output format (default: json)
+
+network options:
+  --timeout TIMEOUT     timeout in seconds
+
+See the documentation for more details.
+";
+    let (doc, commands) = parse_argparse(text);
+    assert_debug_snapshot!(doc, @r#"
+    CommandDoc {
+        brief: RichText {
+            parts: [
+                Text(
+                    "Process some files. This tool does a lot of useful things with files.",
+                ),
+            ],
+        },
+        description: Some(
+            RichText {
+                parts: [
+                    Text(
+                        "See the documentation for more details.",
+                    ),
+                ],
+            },
+        ),
+        usage: [
+            Usage {
+                parts: [
+                    Command(
+                        "tool",
+                    ),
+                    Option(
+                        "[-h]",
+                    ),
+                    Option(
+                        "[-v]",
+                    ),
+                    Option(
+                        "[--output-directory-for-generated-files DIR]",
+                    ),
+                    Option(
+                        "[-f {json,yaml}]",
+                    ),
+                    Option(
+                        "[--timeout TIMEOUT]",
+                    ),
+                    Argument(
+                        "{build,run}",
+                    ),
+                    Argument(
+                        "...",
+                    ),
+                ],
+            },
+        ],
+        arguments: [],
+        option_categories: [
+            CategoryDesc {
+                title: "Options",
+                options: [
+                    OptionDesc {
+                        short: Some(
+                            "-h",
+                        ),
+                        long: "--help",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "show this help message and exit",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-v",
+                        ),
+                        long: "--verbose",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "increase output verbosity",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--output-directory-for-generated-files DIR",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "directory where generated files are written; it is created when it does not exist",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-f {json,yaml}",
+                        ),
+                        long: "--format {json,yaml}",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "output format (default: json)",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                ],
+            },
+            CategoryDesc {
+                title: "Network options",
+                options: [
+                    OptionDesc {
+                        short: None,
+                        long: "--timeout TIMEOUT",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "timeout in seconds",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                ],
+            },
+        ],
+        groups: [],
+    }
+    "#);
+    assert_debug_snapshot!(commands, @r#"
+    [
+        CommandOuterDoc {
+            name: "build",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "build the project",
+                    ),
+                ],
+            },
+        },
+        CommandOuterDoc {
+            name: "run",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "run the project",
+                    ),
+                ],
+            },
+        },
+    ]
+    "#);
+}
+
+#[test]
+fn test_parse_argparse_subcommand() {
+    let text = "usage: tool build [-h] [-j JOBS] target
+
+Build the project from sources.
+
+positional arguments:
+  target                build target
+
+options:
+  -h, --help            show this help message and exit
+  -j JOBS, --jobs JOBS  number of parallel jobs
+";
+    let (doc, commands) = parse_argparse(text);
+    assert_debug_snapshot!(doc, @r#"
+    CommandDoc {
+        brief: RichText {
+            parts: [
+                Text(
+                    "Build the project from sources.",
+                ),
+            ],
+        },
+        description: None,
+        usage: [
+            Usage {
+                parts: [
+                    Command(
+                        "tool",
+                    ),
+                    Command(
+                        "build",
+                    ),
+                    Option(
+                        "[-h]",
+                    ),
+                    Option(
+                        "[-j JOBS]",
+                    ),
+                    Argument(
+                        "target",
+                    ),
+                ],
+            },
+        ],
+        arguments: [
+            ArgumentDesc {
+                name: "target",
+                brief: RichText {
+                    parts: [
+                        Text(
+                            "build target",
+                        ),
+                    ],
+                },
+                description: None,
+                info: ValueInfo {
+                    value_name: None,
+                    default: None,
+                    possible_values: [],
+                    env: None,
+                    aliases: [],
+                    value_hint: None,
+                },
+            },
+        ],
+        option_categories: [
+            CategoryDesc {
+                title: "Options",
+                options: [
+                    OptionDesc {
+                        short: Some(
+                            "-h",
+                        ),
+                        long: "--help",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "show this help message and exit",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-j JOBS",
+                        ),
+                        long: "--jobs JOBS",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "number of parallel jobs",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                ],
+            },
+        ],
+        groups: [],
+    }
+    "#);
+    assert!(commands.is_empty());
+}
+
+#[test]
+fn test_parse_argparse_nargs() {
+    let text = "usage: tool run [-h] [args ...]
+
+positional arguments:
+  args        arguments passed to the program
+
+options:
+  -h, --help  show this help message and exit
+";
+    let (doc, commands) = parse_argparse(text);
+    assert_debug_snapshot!(doc, @r#"
+    CommandDoc {
+        brief: RichText {
+            parts: [],
+        },
+        description: None,
+        usage: [
+            Usage {
+                parts: [
+                    Command(
+                        "tool",
+                    ),
+                    Command(
+                        "run",
+                    ),
+                    Option(
+                        "[-h]",
+                    ),
+                    Option(
+                        "[args ...]",
+                    ),
+                ],
+            },
+        ],
+        arguments: [
+            ArgumentDesc {
+                name: "args",
+                brief: RichText {
+                    parts: [
+                        Text(
+                            "arguments passed to the program",
+                        ),
+                    ],
+                },
+                description: None,
+                info: ValueInfo {
+                    value_name: None,
+                    default: None,
+                    possible_values: [],
+                    env: None,
+                    aliases: [],
+                    value_hint: None,
+                },
+            },
+        ],
+        option_categories: [
+            CategoryDesc {
+                title: "Options",
+                options: [
+                    OptionDesc {
+                        short: Some(
+                            "-h",
+                        ),
+                        long: "--help",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "show this help message and exit",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                ],
+            },
+        ],
+        groups: [],
+    }
+    "#);
+    assert!(commands.is_empty());
+}
+
+#[test]
+fn test_parse_argparse_old_style() {
+    let text = "usage: prog [-h] [--foo FOO] {a,b}
+
+positional arguments:
+  {a,b}       mode
+
+optional arguments:
+  -h, --help  show this help message and exit
+  --foo FOO   foo of the prog
+";
+    let (doc, commands) = parse_argparse(text);
+    assert_debug_snapshot!(doc, @r#"
+    CommandDoc {
+        brief: RichText {
+            parts: [],
+        },
+        description: None,
+        usage: [
+            Usage {
+                parts: [
+                    Command(
+                        "prog",
+                    ),
+                    Option(
+                        "[-h]",
+                    ),
+                    Option(
+                        "[--foo FOO]",
+                    ),
+                    Argument(
+                        "{a,b}",
+                    ),
+                ],
+            },
+        ],
+        arguments: [
+            ArgumentDesc {
+                name: "{a,b}",
+                brief: RichText {
+                    parts: [
+                        Text(
+                            "mode",
+                        ),
+                    ],
+                },
+                description: None,
+                info: ValueInfo {
+                    value_name: None,
+                    default: None,
+                    possible_values: [],
+                    env: None,
+                    aliases: [],
+                    value_hint: None,
+                },
+            },
+        ],
+        option_categories: [
+            CategoryDesc {
+                title: "Optional arguments",
+                options: [
+                    OptionDesc {
+                        short: Some(
+                            "-h",
+                        ),
+                        long: "--help",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "show this help message and exit",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--foo FOO",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "foo of the prog",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                ],
+            },
+        ],
+        groups: [],
+    }
+    "#);
+    assert!(commands.is_empty());
+}
@@ -0,0 +1,110 @@
+use anyhow::{Context, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Version of the capture file format; bumped on every incompatible change
+pub const CAPTURE_FORMAT_VERSION: u32 = 1;
+
+/// Recording or replaying of outputs of the program
+#[derive(Default, Debug, Clone)]
+pub enum CaptureMode {
+    /// The program is run and nothing is stored
+    #[default]
+    Disabled,
+    /// The program is run and all its outputs are stored into the file
+    Record(PathBuf),
+    /// Outputs are read from the file; the program is not run at all
+    Replay(PathBuf),
+}
+
+/// Result of a single invocation of the program
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum CapturedOutput {
+    /// Accepted output of the program
+    Output(String),
+    /// The program failed
+    Failure {
+        exit_code: Option<i32>,
+        stderr: String,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+struct CapturedInvocation {
+    /// Arguments following the fixed arguments of the program
+    args: Vec<String>,
+    #[serde(flatten)]
+    result: CapturedOutput,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CaptureJson {
+    format_version: u32,
+    #[serde(default, skip_deserializing)]
+    generator: String,
+    invocations: Vec<CapturedInvocation>,
+}
+
+/// Outputs of the program indexed by arguments; shared by all jobs
+#[derive(Default)]
+pub(crate) struct Capture {
+    invocations: Mutex<BTreeMap<Vec<String>, CapturedOutput>>,
+}
+
+impl Capture {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let input = std::fs::read_to_string(path)
+            .with_context(|| format!("Reading capture `{}` failed", path.display()))?;
+        let json: CaptureJson = serde_json::from_str(&input)
+            .with_context(|| format!("Invalid capture `{}`", path.display()))?;
+        if json.format_version != CAPTURE_FORMAT_VERSION {
+            bail!(
+                "Unsupported capture format version {} (expected {})",
+                json.format_version,
+                CAPTURE_FORMAT_VERSION
+            );
+        }
+        Ok(Capture {
+            invocations: Mutex::new(
+                json.invocations
+                    .into_iter()
+                    .map(|i| (i.args, i.result))
+                    .collect(),
+            ),
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        // Invocations are sorted by arguments, so the file does not depend on the order of jobs
+        let json = CaptureJson {
+            format_version: CAPTURE_FORMAT_VERSION,
+            generator: format!("cli-doc {}", env!("CARGO_PKG_VERSION")),
+            invocations: self
+                .invocations
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(args, result)| CapturedInvocation {
+                    args: args.clone(),
+                    result: result.clone(),
+                })
+                .collect(),
+        };
+        std::fs::write(path, serde_json::to_string_pretty(&json)?)
+            .with_context(|| format!("Writing capture `{}` failed", path.display()))
+    }
+
+    pub fn get(&self, args: &[String]) -> Option<CapturedOutput> {
+        self.invocations.lock().unwrap().get(args).cloned()
+    }
+
+    pub fn insert(&self, args: &[String], result: CapturedOutput) {
+        self.invocations
+            .lock()
+            .unwrap()
+            .insert(args.to_vec(), result);
+    }
+}
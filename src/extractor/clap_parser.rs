@@ -1,19 +1,28 @@
 use crate::commands::{
     ArgumentDesc, CategoryDesc, CommandDoc, CommandOuterDoc, OptionDesc, Usage, UsagePart,
+    ValueInfo,
 };
+use crate::extractor::parser::HelpParser;
 use crate::extractor::sections::Section;
+use crate::extractor::usage::split_short_long;
 use crate::text::RichText;
 use anyhow::bail;
 use itertools::Either;
 
 pub(crate) struct ClapParser {}
 
-impl ClapParser {
-    pub fn new() -> Self {
-        Self {}
+impl HelpParser for ClapParser {
+    fn name(&self) -> &'static str {
+        "clap"
+    }
+
+    fn detect(&self, sections: &[Section]) -> bool {
+        sections
+            .iter()
+            .any(|s| s.first_line().starts_with("Usage: "))
     }
 
-    pub fn parse(
+    fn parse(
         &self,
         sections: &mut [Section],
     ) -> anyhow::Result<(CommandDoc, Vec<CommandOuterDoc>)> {
@@ -56,10 +65,17 @@ impl ClapParser {
                 usage: usage.iter().map(|s| parse_usage(s)).collect(),
                 arguments,
                 option_categories,
+                groups: Vec::new(),
             },
             commands,
         ))
     }
+}
+
+impl ClapParser {
+    pub const fn new() -> Self {
+        Self {}
+    }
 
     fn extract_options(&self, sections: &mut [Section]) -> Vec<CategoryDesc> {
         let mut option_sections = Vec::new();
@@ -81,7 +97,7 @@ impl ClapParser {
                 .flat_map(|s| {
                     if s.lines().len() == 1 {
                         let (left, right) = split_once2(s.first_line(), "  ");
-                        let (short, long) = split_short_long(left);
+                        let (short, long) = split_names(left);
                         let (brief, description) = if right.is_empty() {
                             s.subsections_as_brief_and_full_description()
                         } else {
@@ -93,19 +109,21 @@ impl ClapParser {
                                 long,
                                 brief,
                                 description,
+                                info: ValueInfo::default(),
                             })
                             .into_iter(),
                         )
                     } else {
                         Either::Right(s.lines().iter().map(|s| {
                             let (left, right) = split_once2(s, "  ");
-                            let (short, long) = split_short_long(left);
+                            let (short, long) = split_names(left);
                             let brief = RichText::from_single_line(right.trim());
                             OptionDesc {
                                 short,
                                 long,
                                 brief,
                                 description: None,
+                                info: ValueInfo::default(),
                             }
                         }))
                     }
@@ -134,6 +152,7 @@ impl ClapParser {
                             name: left.trim().to_string(),
                             brief: RichText::from_single_line(right.trim()),
                             description: None,
+                            info: ValueInfo::default(),
                         }
                     } else {
                         let (brief, description) = s.subsections_as_brief_and_full_description();
@@ -141,6 +160,7 @@ impl ClapParser {
                             name: s.paragraph(),
                             brief,
                             description,
+                            info: ValueInfo::default(),
                         }
                     }
                 })
@@ -158,28 +178,29 @@ impl ClapParser {
             section
                 .flatten_child_lines()
                 .filter_map(|line| {
-                    let (raw_name, _desc) = line.split_once("  ")?;
-                    let (_short, name) = if let Some((first, second)) = raw_name.split_once(",") {
-                        let first = first.trim().to_string();
-                        let second = second.trim().to_string();
-                        if first.len() > second.len() {
-                            (Some(second), first)
-                        } else {
-                            (Some(first), second)
-                        }
-                    } else {
-                        (None, raw_name.trim().to_string())
-                    };
+                    let (raw_name, desc) = line.split_once("  ")?;
+                    let mut names: Vec<String> =
+                        raw_name.split(',').map(|s| s.trim().to_string()).collect();
+                    // The longest name is the command itself, e.g. "r, run" or "run, -r, --run"
+                    let idx = names
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, n)| !n.starts_with('-'))
+                        .max_by_key(|(i, n)| (n.len(), std::cmp::Reverse(*i)))?
+                        .0;
+                    let name = names.remove(idx);
                     if !name
                         .chars()
                         .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
                     {
                         return None;
                     }
+                    let (desc, visible_aliases) = split_aliases(desc.trim());
+                    names.extend(visible_aliases);
                     Some(CommandOuterDoc {
                         name,
-                        /*short,
-                        description: desc.trim().to_string(),*/
+                        aliases: names,
+                        brief: RichText::from_single_line(desc),
                     })
                 })
                 .collect()
@@ -189,7 +210,7 @@ impl ClapParser {
     }
 }
 
-fn parse_usage(input: &str) -> Usage {
+pub(crate) fn parse_usage(input: &str) -> Usage {
     Usage {
         parts: input
             .split_whitespace()
@@ -206,6 +227,23 @@ fn parse_usage(input: &str) -> Usage {
     }
 }
 
+/// Splits "Run something [aliases: r, x]" into the text and the aliases
+fn split_aliases(desc: &str) -> (&str, Vec<String>) {
+    if let Some(rest) = desc.strip_suffix(']')
+        && let Some(start) = rest.rfind('[')
+        && let Some(aliases) = rest[start + 1..]
+            .strip_prefix("aliases: ")
+            .or_else(|| rest[start + 1..].strip_prefix("alias: "))
+    {
+        (
+            rest[..start].trim_end(),
+            aliases.split(", ").map(|s| s.to_string()).collect(),
+        )
+    } else {
+        (desc, Vec::new())
+    }
+}
+
 fn split_once2<'a>(s: &'a str, sep: &str) -> (&'a str, &'a str) {
     if let Some((left, right)) = s.split_once(sep) {
         (left, right)
@@ -214,12 +252,8 @@ fn split_once2<'a>(s: &'a str, sep: &str) -> (&'a str, &'a str) {
     }
 }
 
-fn split_short_long(s: &str) -> (Option<String>, String) {
-    if let Some((first, second)) = s.split_once(',') {
-        let first = first.trim().to_string();
-        let second = second.trim().to_string();
-        (Some(first), second)
-    } else {
-        (None, s.trim().to_string())
-    }
+/// Clap prints the short name first, e.g. "-c, --color <WHEN>"
+fn split_names(s: &str) -> (Option<String>, String) {
+    let names: Vec<_> = s.splitn(2, ',').map(str::trim).collect();
+    split_short_long(&names)
 }
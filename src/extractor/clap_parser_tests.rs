@@ -1,5 +1,6 @@
 use crate::commands::{CommandDoc, CommandOuterDoc};
 use crate::extractor::clap_parser::ClapParser;
+use crate::extractor::parser::HelpParser;
 use crate::extractor::sections::parse_sections;
 use insta::assert_debug_snapshot;
 
@@ -63,6 +64,14 @@ Options:
                             ],
                         },
                         description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                     OptionDesc {
                         short: Some(
@@ -77,6 +86,14 @@ Options:
                             ],
                         },
                         description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                     OptionDesc {
                         short: Some(
@@ -91,10 +108,19 @@ Options:
                             ],
                         },
                         description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                 ],
             },
         ],
+        groups: [],
     }
     "#);
     assert!(commands.is_empty());
@@ -154,6 +180,14 @@ Options:
                     ],
                 },
                 description: None,
+                info: ValueInfo {
+                    value_name: None,
+                    default: None,
+                    possible_values: [],
+                    env: None,
+                    aliases: [],
+                    value_hint: None,
+                },
             },
         ],
         option_categories: [
@@ -171,10 +205,19 @@ Options:
                             ],
                         },
                         description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                 ],
             },
         ],
+        groups: [],
     }
     "#);
     assert!(commands.is_empty());
@@ -306,6 +349,14 @@ See 'cargo help <command>' for more information on a specific command.
                             ],
                         },
                         description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                     OptionDesc {
                         short: None,
@@ -318,6 +369,14 @@ See 'cargo help <command>' for more information on a specific command.
                             ],
                         },
                         description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                     OptionDesc {
                         short: None,
@@ -330,6 +389,14 @@ See 'cargo help <command>' for more information on a specific command.
                             ],
                         },
                         description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                     OptionDesc {
                         short: Some(
@@ -344,6 +411,14 @@ See 'cargo help <command>' for more information on a specific command.
                             ],
                         },
                         description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                     OptionDesc {
                         short: Some(
@@ -358,6 +433,14 @@ See 'cargo help <command>' for more information on a specific command.
                             ],
                         },
                         description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                     OptionDesc {
                         short: None,
@@ -370,6 +453,14 @@ See 'cargo help <command>' for more information on a specific command.
                             ],
                         },
                         description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                     OptionDesc {
                         short: None,
@@ -382,6 +473,14 @@ See 'cargo help <command>' for more information on a specific command.
                             ],
                         },
                         description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                     OptionDesc {
                         short: None,
@@ -394,6 +493,14 @@ See 'cargo help <command>' for more information on a specific command.
                             ],
                         },
                         description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                     OptionDesc {
                         short: None,
@@ -406,6 +513,14 @@ See 'cargo help <command>' for more information on a specific command.
                             ],
                         },
                         description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                     OptionDesc {
                         short: None,
@@ -418,6 +533,14 @@ See 'cargo help <command>' for more information on a specific command.
                             ],
                         },
                         description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                     OptionDesc {
                         short: None,
@@ -430,6 +553,14 @@ See 'cargo help <command>' for more information on a specific command.
                             ],
                         },
                         description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                     OptionDesc {
                         short: None,
@@ -442,6 +573,14 @@ See 'cargo help <command>' for more information on a specific command.
                             ],
                         },
                         description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                     OptionDesc {
                         short: Some(
@@ -456,64 +595,211 @@ See 'cargo help <command>' for more information on a specific command.
                             ],
                         },
                         description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                 ],
             },
         ],
+        groups: [],
     }
     "#);
-    assert_debug_snapshot!(commands, @r###"
+    assert_debug_snapshot!(commands, @r#"
     [
         CommandOuterDoc {
             name: "build",
+            aliases: [
+                "b",
+            ],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Compile the current package",
+                    ),
+                ],
+            },
         },
         CommandOuterDoc {
             name: "check",
+            aliases: [
+                "c",
+            ],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Analyze the current package and report errors, but don't build object files",
+                    ),
+                ],
+            },
         },
         CommandOuterDoc {
             name: "clean",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Remove the target directory",
+                    ),
+                ],
+            },
         },
         CommandOuterDoc {
             name: "doc",
+            aliases: [
+                "d",
+            ],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Build this package's and its dependencies' documentation",
+                    ),
+                ],
+            },
         },
         CommandOuterDoc {
             name: "new",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Create a new cargo package",
+                    ),
+                ],
+            },
         },
         CommandOuterDoc {
             name: "init",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Create a new cargo package in an existing directory",
+                    ),
+                ],
+            },
         },
         CommandOuterDoc {
             name: "add",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Add dependencies to a manifest file",
+                    ),
+                ],
+            },
         },
         CommandOuterDoc {
             name: "remove",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Remove dependencies from a manifest file",
+                    ),
+                ],
+            },
         },
         CommandOuterDoc {
             name: "run",
+            aliases: [
+                "r",
+            ],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Run a binary or example of the local package",
+                    ),
+                ],
+            },
         },
         CommandOuterDoc {
             name: "test",
+            aliases: [
+                "t",
+            ],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Run the tests",
+                    ),
+                ],
+            },
         },
         CommandOuterDoc {
             name: "bench",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Run the benchmarks",
+                    ),
+                ],
+            },
         },
         CommandOuterDoc {
             name: "update",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Update dependencies listed in Cargo.lock",
+                    ),
+                ],
+            },
         },
         CommandOuterDoc {
             name: "search",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Search registry for crates",
+                    ),
+                ],
+            },
         },
         CommandOuterDoc {
             name: "publish",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Package and upload this package to the registry",
+                    ),
+                ],
+            },
         },
         CommandOuterDoc {
             name: "install",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Install a Rust binary",
+                    ),
+                ],
+            },
         },
         CommandOuterDoc {
             name: "uninstall",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Uninstall a Rust binary",
+                    ),
+                ],
+            },
         },
     ]
-    "###);
+    "#);
 }
 
 #[test]
@@ -617,6 +903,14 @@ GLOBAL OPTIONS:
                             ],
                         },
                         description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                 ],
             },
@@ -643,6 +937,14 @@ GLOBAL OPTIONS:
                                 ],
                             },
                         ),
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                     OptionDesc {
                         short: None,
@@ -674,6 +976,14 @@ GLOBAL OPTIONS:
                                 ],
                             },
                         ),
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                     OptionDesc {
                         short: None,
@@ -703,6 +1013,14 @@ GLOBAL OPTIONS:
                                 ],
                             },
                         ),
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                     OptionDesc {
                         short: None,
@@ -724,28 +1042,69 @@ GLOBAL OPTIONS:
                                 ],
                             },
                         ),
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
                     },
                 ],
             },
         ],
+        groups: [],
     }
     "#);
-    assert_debug_snapshot!(commands, @r###"
+    assert_debug_snapshot!(commands, @r#"
     [
         CommandOuterDoc {
             name: "start",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Start the server",
+                    ),
+                ],
+            },
         },
         CommandOuterDoc {
             name: "stop",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Stop the server",
+                    ),
+                ],
+            },
         },
         CommandOuterDoc {
             name: "info",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Show info of a running server",
+                    ),
+                ],
+            },
         },
         CommandOuterDoc {
             name: "generate-access",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Generate an access file without starting the server",
+                    ),
+                ],
+            },
         },
     ]
-    "###);
+    "#);
 }
 
 #[test]
@@ -793,9 +1152,18 @@ Arguments:
                     parts: [],
                 },
                 description: None,
+                info: ValueInfo {
+                    value_name: None,
+                    default: None,
+                    possible_values: [],
+                    env: None,
+                    aliases: [],
+                    value_hint: None,
+                },
             },
         ],
         option_categories: [],
+        groups: [],
     }
     "#);
     assert!(commands.is_empty());
@@ -888,6 +1256,7 @@ Usage: cargo add [OPTIONS] <DEP>[@<VERSION>] ...
             ],
             arguments: [],
             option_categories: [],
+            groups: [],
         }
         "#);
     assert!(commands.is_empty());
@@ -923,6 +1292,65 @@ fn test_parse_clap_only_usage() {
         ],
         arguments: [],
         option_categories: [],
+        groups: [],
     }
     "###);
 }
+
+#[test]
+fn test_parse_clap_command_aliases() {
+    let text = "Usage: prog <COMMAND>
+
+Commands:
+  build, b       Build the project
+  run, -r, --run  Run the project [aliases: start, go]
+  test           Test the project [alias: t]
+";
+    let (_doc, commands) = parse_clap(text);
+    assert_debug_snapshot!(commands, @r#"
+    [
+        CommandOuterDoc {
+            name: "build",
+            aliases: [
+                "b",
+            ],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Build the project",
+                    ),
+                ],
+            },
+        },
+        CommandOuterDoc {
+            name: "run",
+            aliases: [
+                "-r",
+                "--run",
+                "start",
+                "go",
+            ],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Run the project",
+                    ),
+                ],
+            },
+        },
+        CommandOuterDoc {
+            name: "test",
+            aliases: [
+                "t",
+            ],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Test the project",
+                    ),
+                ],
+            },
+        },
+    ]
+    "#);
+}
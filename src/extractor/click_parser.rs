@@ -0,0 +1,115 @@
+use crate::commands::{CategoryDesc, CommandDoc, CommandOuterDoc, Usage, UsagePart};
+use crate::extractor::cobra_parser::parse_flags;
+use crate::extractor::parser::HelpParser;
+use crate::extractor::sections::Section;
+use crate::extractor::usage::{parse_command_list, parse_grouped_usage, split_brief};
+use crate::text::RichText;
+use anyhow::bail;
+
+/// Indentation of the description and of the option tables in Click's help
+const CLICK_INDENT: usize = 2;
+
+/// Parser of help generated by Python `click`
+pub(crate) struct ClickParser {}
+
+impl ClickParser {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+impl HelpParser for ClickParser {
+    fn name(&self) -> &'static str {
+        "click"
+    }
+
+    fn detect(&self, sections: &[Section]) -> bool {
+        let Some(first) = sections.first() else {
+            return false;
+        };
+        if !first.first_line().starts_with("Usage: ") {
+            return false;
+        }
+        // Unlike clap, the description follows the usage and is indented
+        first
+            .subsections()
+            .iter()
+            .any(|s| s.indent() == CLICK_INDENT)
+            || sections.iter().any(|s| {
+                s.flatten_child_lines()
+                    .any(|line| line.ends_with("Show this message and exit."))
+            })
+    }
+
+    fn parse(
+        &self,
+        sections: &mut [Section],
+    ) -> anyhow::Result<(CommandDoc, Vec<CommandOuterDoc>)> {
+        let Some(first) = sections.first() else {
+            bail!("Input is empty");
+        };
+        let Some(usage_start) = first.first_line().strip_prefix("Usage: ") else {
+            bail!("Usage section not found");
+        };
+        // Long usages are wrapped and aligned after the program name
+        let mut usage = usage_start.trim().to_string();
+        let mut text = RichText::new();
+        for s in first.subsections() {
+            if s.indent() > CLICK_INDENT && text.is_empty() {
+                for line in s.lines() {
+                    usage.push(' ');
+                    usage.push_str(line.trim());
+                }
+            } else {
+                s.as_rich_text_into(&mut text);
+            }
+        }
+
+        let mut option_categories = Vec::new();
+        let mut commands = Vec::new();
+        for section in &sections[1..] {
+            let Some(title) = section.first_line().strip_suffix(':') else {
+                continue;
+            };
+            if title == "Commands" {
+                commands.extend(parse_command_list(section));
+                continue;
+            }
+            let options = parse_flags(section);
+            if !options.is_empty() {
+                option_categories.push(CategoryDesc {
+                    title: title.to_string(),
+                    options,
+                });
+            }
+        }
+
+        let (brief, description) = split_brief(text);
+
+        Ok((
+            CommandDoc {
+                brief,
+                description,
+                usage: vec![parse_click_usage(&usage)],
+                arguments: Vec::new(),
+                option_categories,
+                groups: Vec::new(),
+            },
+            commands,
+        ))
+    }
+}
+
+/// Click puts optional arguments into brackets too, e.g. "prog [OPTIONS] [SRC]..."
+fn parse_click_usage(input: &str) -> Usage {
+    let mut usage = parse_grouped_usage(input);
+    for part in &mut usage.parts {
+        if let UsagePart::Option(s) = part
+            && s != "[OPTIONS]"
+            && !s.starts_with("[-")
+        {
+            *part = UsagePart::Argument(std::mem::take(s));
+        }
+    }
+    usage
+}
@@ -0,0 +1,498 @@
+use crate::commands::{CommandDoc, CommandOuterDoc};
+use crate::extractor::click_parser::ClickParser;
+use crate::extractor::parser::HelpParser;
+use crate::extractor::sections::parse_sections;
+use insta::assert_debug_snapshot;
+
+fn parse_click(text: &str) -> (CommandDoc, Vec<CommandOuterDoc>) {
+    let mut sections = parse_sections(text);
+    ClickParser::new().parse(&mut sections).unwrap()
+}
+
+#[test]
+fn test_parse_click_group() {
+    let text = r#"Usage: tool [OPTIONS] COMMAND [ARGS]...
+
+  Build and deploy static sites.
+
+  The tool reads the configuration from the current directory and runs the
+  selected command.
+
+Options:
+  --debug / --no-debug  Enable debug output.
+  -c, --config PATH     Path to the configuration file.  [env var:
+                        TOOL_CONFIG]
+  --version             Show the version and exit.
+  --help                Show this message and exit.
+
+Commands:
+  build        Build the site.
+  cache        Manage the cache.
+  deploy-site  Deploy the site to TARGET.
+"#;
+    let (doc, commands) = parse_click(text);
+    assert_debug_snapshot!(doc, @r#"
+    CommandDoc {
+        brief: RichText {
+            parts: [
+                Text(
+                    "Build and deploy static sites.",
+                ),
+            ],
+        },
+        description: Some(
+            RichText {
+                parts: [
+                    Text(
+                        "The tool reads the configuration from the current directory and runs the selected command.",
+                    ),
+                ],
+            },
+        ),
+        usage: [
+            Usage {
+                parts: [
+                    Command(
+                        "tool",
+                    ),
+                    Option(
+                        "[OPTIONS]",
+                    ),
+                    Argument(
+                        "COMMAND",
+                    ),
+                    Argument(
+                        "[ARGS]...",
+                    ),
+                ],
+            },
+        ],
+        arguments: [],
+        option_categories: [
+            CategoryDesc {
+                title: "Options",
+                options: [
+                    OptionDesc {
+                        short: None,
+                        long: "--debug / --no-debug",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Enable debug output.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-c",
+                        ),
+                        long: "--config PATH",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Path to the configuration file.  [env var: TOOL_CONFIG]",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--version",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Show the version and exit.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--help",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Show this message and exit.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                ],
+            },
+        ],
+        groups: [],
+    }
+    "#);
+    assert_debug_snapshot!(commands, @r#"
+    [
+        CommandOuterDoc {
+            name: "build",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Build the site.",
+                    ),
+                ],
+            },
+        },
+        CommandOuterDoc {
+            name: "cache",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Manage the cache.",
+                    ),
+                ],
+            },
+        },
+        CommandOuterDoc {
+            name: "deploy-site",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Deploy the site to TARGET.",
+                    ),
+                ],
+            },
+        },
+    ]
+    "#);
+}
+
+#[test]
+fn test_parse_click_command() {
+    let text = r#"Usage: tool build [OPTIONS] [SRC]...
+
+  Build the site.
+
+Options:
+  -n, --count INTEGER  Number of parallel jobs.  [default: 1]
+  --mode [fast|slow]   Build mode.  [default: fast]
+  -o, --output DIR     Output directory which will be created if it does not
+                       exist yet, the existing content is removed first.
+                       [required]
+  -v, --verbose        Increase verbosity.
+  --help               Show this message and exit.
+"#;
+    let (doc, commands) = parse_click(text);
+    assert_debug_snapshot!(doc, @r#"
+    CommandDoc {
+        brief: RichText {
+            parts: [
+                Text(
+                    "Build the site.",
+                ),
+            ],
+        },
+        description: None,
+        usage: [
+            Usage {
+                parts: [
+                    Command(
+                        "tool",
+                    ),
+                    Command(
+                        "build",
+                    ),
+                    Option(
+                        "[OPTIONS]",
+                    ),
+                    Argument(
+                        "[SRC]...",
+                    ),
+                ],
+            },
+        ],
+        arguments: [],
+        option_categories: [
+            CategoryDesc {
+                title: "Options",
+                options: [
+                    OptionDesc {
+                        short: Some(
+                            "-n",
+                        ),
+                        long: "--count INTEGER",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Number of parallel jobs.  [default: 1]",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--mode [fast|slow]",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Build mode.  [default: fast]",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-o",
+                        ),
+                        long: "--output DIR",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Output directory which will be created if it does not exist yet, the existing content is removed first. [required]",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-v",
+                        ),
+                        long: "--verbose",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Increase verbosity.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--help",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Show this message and exit.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                ],
+            },
+        ],
+        groups: [],
+    }
+    "#);
+    assert_debug_snapshot!(commands, @"[]");
+}
+
+#[test]
+fn test_parse_click_wrapped() {
+    let text = r#"Usage: merge [OPTIONS] FIRST_INPUT_FILE
+               SECOND_INPUT_FILE
+               [OUTPUT_DESTINATION_DIRECTORY]
+
+  Merge two files.
+
+  Examples:
+    merge a.txt b.txt
+    merge a.txt b.txt out/
+
+Options:
+  --a-really-long-option-name-here INTEGER RANGE
+                                  Long option whose help
+                                  is placed on the next
+                                  line.  [0<=x<=10]
+  --flag
+  --help                          Show this message and
+                                  exit.
+"#;
+    let (doc, commands) = parse_click(text);
+    assert_debug_snapshot!(doc, @r#"
+    CommandDoc {
+        brief: RichText {
+            parts: [
+                Text(
+                    "Merge two files.",
+                ),
+            ],
+        },
+        description: Some(
+            RichText {
+                parts: [
+                    Text(
+                        "Examples:",
+                    ),
+                    Text(
+                        "merge a.txt b.txt merge a.txt b.txt out/",
+                    ),
+                ],
+            },
+        ),
+        usage: [
+            Usage {
+                parts: [
+                    Command(
+                        "merge",
+                    ),
+                    Option(
+                        "[OPTIONS]",
+                    ),
+                    Argument(
+                        "FIRST_INPUT_FILE",
+                    ),
+                    Argument(
+                        "SECOND_INPUT_FILE",
+                    ),
+                    Argument(
+                        "[OUTPUT_DESTINATION_DIRECTORY]",
+                    ),
+                ],
+            },
+        ],
+        arguments: [],
+        option_categories: [
+            CategoryDesc {
+                title: "Options",
+                options: [
+                    OptionDesc {
+                        short: None,
+                        long: "--a-really-long-option-name-here INTEGER RANGE",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Long option whose help is placed on the next line.  [0<=x<=10]",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--flag",
+                        brief: RichText {
+                            parts: [],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--help",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Show this message and exit.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                ],
+            },
+        ],
+        groups: [],
+    }
+    "#);
+    assert_debug_snapshot!(commands, @"[]");
+}
@@ -0,0 +1,153 @@
+use crate::commands::{CategoryDesc, CommandDoc, CommandOuterDoc, OptionDesc, ValueInfo};
+use crate::extractor::parser::HelpParser;
+use crate::extractor::sections::Section;
+use crate::extractor::usage::parse_grouped_usage;
+use crate::extractor::usage::{group_title, parse_command_list, split_brief, split_short_long};
+use crate::text::RichText;
+use anyhow::bail;
+
+/// Parser of help generated by Go `cobra` (with `pflag` flags)
+pub(crate) struct CobraParser {}
+
+impl CobraParser {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+impl HelpParser for CobraParser {
+    fn name(&self) -> &'static str {
+        "cobra"
+    }
+
+    fn detect(&self, sections: &[Section]) -> bool {
+        sections.iter().any(|s| {
+            (s.first_line() == "Usage:" && !s.subsections().is_empty())
+                || s.first_line().starts_with("Usage:  ")
+        })
+    }
+
+    fn parse(
+        &self,
+        sections: &mut [Section],
+    ) -> anyhow::Result<(CommandDoc, Vec<CommandOuterDoc>)> {
+        let mut text = RichText::new();
+        let mut usage = None;
+        let mut option_categories = Vec::new();
+        let mut commands = Vec::new();
+        for section in sections.iter() {
+            let first_line = section.first_line();
+            if first_line == "Usage:" {
+                usage = Some(
+                    section
+                        .flatten_child_lines()
+                        .map(parse_grouped_usage)
+                        .collect(),
+                );
+                continue;
+            }
+            // Variant with usage on the same line, e.g. "Usage:  docker [OPTIONS] COMMAND"
+            if let Some(u) = first_line.strip_prefix("Usage:  ") {
+                usage = Some(vec![parse_grouped_usage(u)]);
+                continue;
+            }
+            let Some(title) = group_title(section) else {
+                if !first_line.starts_with("Use \"") {
+                    section.as_rich_text_into(&mut text);
+                }
+                continue;
+            };
+            match title {
+                "Aliases" | "Examples" | "Additional help topics" => {}
+                title if title.contains("Commands") => {
+                    commands.extend(parse_command_list(section));
+                }
+                title => {
+                    let options = parse_flags(section);
+                    if !options.is_empty() {
+                        option_categories.push(CategoryDesc {
+                            title: title.to_string(),
+                            options,
+                        });
+                    }
+                }
+            }
+        }
+        let Some(usage) = usage else {
+            bail!("Usage section not found");
+        };
+
+        let (brief, description) = split_brief(text);
+
+        Ok((
+            CommandDoc {
+                brief,
+                description,
+                usage,
+                arguments: Vec::new(),
+                option_categories,
+                groups: Vec::new(),
+            },
+            commands,
+        ))
+    }
+}
+
+/// Offset of the help text in a flag line, e.g. "-o, --output string   Output format"
+fn help_offset(line: &str) -> Option<usize> {
+    let pos = line.find("  ")?;
+    Some(line.len() - line[pos..].trim_start().len())
+}
+
+/// Parses a table of flags where each flag starts with '-' and its help may continue
+/// on the following lines aligned to the help column
+pub(crate) fn parse_flags(section: &Section) -> Vec<OptionDesc> {
+    let lines = section.child_lines_with_indent();
+    let Some(base) = lines.iter().map(|(indent, _)| *indent).min() else {
+        return Vec::new();
+    };
+    // Continuation lines of multi-line usages are aligned to the help column
+    let help_column = lines
+        .iter()
+        .filter(|(_, line)| line.starts_with('-'))
+        .filter_map(|(indent, line)| help_offset(line).map(|offset| indent + offset))
+        .min();
+
+    let mut flags: Vec<(&str, Vec<&str>)> = Vec::new();
+    for (indent, line) in lines {
+        let is_flag = line.starts_with('-')
+            && match help_column {
+                Some(column) => indent < column,
+                None => indent == base,
+            };
+        if is_flag {
+            let (names, help) = match help_offset(line) {
+                Some(offset) => (line[..offset].trim_end(), vec![&line[offset..]]),
+                None => (line, Vec::new()),
+            };
+            flags.push((names, help));
+        } else if let Some((_, help)) = flags.last_mut() {
+            help.push(line);
+        }
+    }
+
+    flags
+        .into_iter()
+        .map(|(names, help)| {
+            // kubectl style: "--output='yaml':"
+            let names: Vec<&str> = names.trim_end_matches(':').split(", ").collect();
+            let (short, long) = split_short_long(&names);
+            let mut brief = RichText::new();
+            if !help.is_empty() {
+                brief.add_lines(&[help.join(" ").as_str()]);
+            }
+            OptionDesc {
+                short,
+                long,
+                brief,
+                description: None,
+                info: ValueInfo::default(),
+            }
+        })
+        .collect()
+}
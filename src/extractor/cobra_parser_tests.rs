@@ -0,0 +1,993 @@
+use crate::commands::{CommandDoc, CommandOuterDoc};
+use crate::extractor::cobra_parser::CobraParser;
+use crate::extractor::parser::HelpParser;
+use crate::extractor::sections::{expand_leading_tabs, parse_sections};
+use insta::assert_debug_snapshot;
+
+fn parse_cobra(text: &str) -> (CommandDoc, Vec<CommandOuterDoc>) {
+    let text = expand_leading_tabs(text);
+    let mut sections = parse_sections(&text);
+    CobraParser::new().parse(&mut sections).unwrap()
+}
+
+#[test]
+fn test_parse_cobra_hugo() {
+    let text = "Hugo is a Fast and Flexible Static Site Generator built with love by spf13 and friends in Go.
+
+Complete documentation is available at https://gohugo.io/.
+
+Usage:
+  hugo [flags]
+  hugo [command]
+
+Available Commands:
+  completion  Generate the autocompletion script for the specified shell
+  config      Print the site configuration
+  help        Help about any command
+  server      A high performance webserver
+
+Flags:
+  -b, --baseURL string             hostname (and path) to the root, e.g. https://spf13.com/
+  -D, --buildDrafts                include content marked as draft
+      --cacheDir string            filesystem path to cache directory
+      --config string              config file (default is hugo.yaml|json|toml)
+  -e, --environment string         build environment
+      --templateMetrics            display metrics about template executions
+  -h, --help                       help for hugo
+      --ignoreVendorPaths string   ignores any _vendor for module paths matching the given Glob pattern
+      --logLevel string            log level (debug|info|warn|error)
+                                   multi-line usage is aligned to the help column
+
+Additional help topics:
+  hugo check    Contains some verification checks
+
+Use \"hugo [command] --help\" for more information about a command.
+";
+    let (doc, commands) = parse_cobra(text);
+    assert_debug_snapshot!(doc, @r#"
+    CommandDoc {
+        brief: RichText {
+            parts: [
+                Text(
+                    "Hugo is a Fast and Flexible Static Site Generator built with love by spf13 and friends in Go.",
+                ),
+            ],
+        },
+        description: Some(
+            RichText {
+                parts: [
+                    Text(
+                        "Complete documentation is available at https://gohugo.io/.",
+                    ),
+                ],
+            },
+        ),
+        usage: [
+            Usage {
+                parts: [
+                    Command(
+                        "hugo",
+                    ),
+                    Option(
+                        "[flags]",
+                    ),
+                ],
+            },
+            Usage {
+                parts: [
+                    Command(
+                        "hugo",
+                    ),
+                    Option(
+                        "[command]",
+                    ),
+                ],
+            },
+        ],
+        arguments: [],
+        option_categories: [
+            CategoryDesc {
+                title: "Flags",
+                options: [
+                    OptionDesc {
+                        short: Some(
+                            "-b",
+                        ),
+                        long: "--baseURL string",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "hostname (and path) to the root, e.g. https://spf13.com/",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-D",
+                        ),
+                        long: "--buildDrafts",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "include content marked as draft",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--cacheDir string",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "filesystem path to cache directory",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--config string",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "config file (default is hugo.yaml|json|toml)",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-e",
+                        ),
+                        long: "--environment string",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "build environment",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--templateMetrics",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "display metrics about template executions",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-h",
+                        ),
+                        long: "--help",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "help for hugo",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--ignoreVendorPaths string",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "ignores any _vendor for module paths matching the given Glob pattern",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--logLevel string",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "log level (debug|info|warn|error) multi-line usage is aligned to the help column",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                ],
+            },
+        ],
+        groups: [],
+    }
+    "#);
+    assert_debug_snapshot!(commands, @r#"
+    [
+        CommandOuterDoc {
+            name: "completion",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Generate the autocompletion script for the specified shell",
+                    ),
+                ],
+            },
+        },
+        CommandOuterDoc {
+            name: "config",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Print the site configuration",
+                    ),
+                ],
+            },
+        },
+        CommandOuterDoc {
+            name: "help",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "Help about any command",
+                    ),
+                ],
+            },
+        },
+        CommandOuterDoc {
+            name: "server",
+            aliases: [],
+            brief: RichText {
+                parts: [
+                    Text(
+                        "A high performance webserver",
+                    ),
+                ],
+            },
+        },
+    ]
+    "#);
+}
+
+#[test]
+fn test_parse_cobra_hugo_server() {
+    let text = "Hugo provides its own webserver which builds and serves the site.
+
+Usage:
+  hugo server [command] [flags]
+
+Aliases:
+  server, serve
+
+Examples:
+  hugo server -D
+
+Flags:
+      --appendPort             append port to baseURL (default true)
+  -p, --port int               port on which the server will listen (default 1313)
+
+Global Flags:
+      --config string          config file (default is hugo.yaml|json|toml)
+      --quiet                  build in quiet mode
+";
+    let (doc, commands) = parse_cobra(text);
+    assert_debug_snapshot!(doc, @r#"
+    CommandDoc {
+        brief: RichText {
+            parts: [
+                Text(
+                    "Hugo provides its own webserver which builds and serves the site.",
+                ),
+            ],
+        },
+        description: None,
+        usage: [
+            Usage {
+                parts: [
+                    Command(
+                        "hugo",
+                    ),
+                    Command(
+                        "server",
+                    ),
+                    Option(
+                        "[command]",
+                    ),
+                    Option(
+                        "[flags]",
+                    ),
+                ],
+            },
+        ],
+        arguments: [],
+        option_categories: [
+            CategoryDesc {
+                title: "Flags",
+                options: [
+                    OptionDesc {
+                        short: None,
+                        long: "--appendPort",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "append port to baseURL (default true)",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-p",
+                        ),
+                        long: "--port int",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "port on which the server will listen (default 1313)",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                ],
+            },
+            CategoryDesc {
+                title: "Global Flags",
+                options: [
+                    OptionDesc {
+                        short: None,
+                        long: "--config string",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "config file (default is hugo.yaml|json|toml)",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--quiet",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "build in quiet mode",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                ],
+            },
+        ],
+        groups: [],
+    }
+    "#);
+    assert!(commands.is_empty());
+}
+
+#[test]
+fn test_parse_cobra_docker_container_ls() {
+    let text = "Usage:  docker container ls [OPTIONS]
+
+List containers
+
+Aliases:
+  docker container ls, docker container list, docker container ps, docker ps
+
+Options:
+  -a, --all             Show all containers (default shows just running)
+  -f, --filter filter   Filter output based on conditions provided
+      --format string   Format output using a custom template:
+                        'table':            Print output in table format
+                        with column headers (default)
+                        'table TEMPLATE':   Print output in table format
+                        using the given Go template
+                        'json':             Print in JSON format
+                        'TEMPLATE':         Print output using the given
+                        Go template.
+                        Refer to https://docs.docker.com/go/formatting/
+                        for more information about formatting output with
+                        templates
+  -n, --last int        Show n last created containers (includes all
+                        states) (default -1)
+  -l, --latest          Show the latest created container (includes all
+                        states)
+      --no-trunc        Don't truncate output
+  -q, --quiet           Only display container IDs
+  -s, --size            Display total file sizes
+";
+    let (doc, commands) = parse_cobra(text);
+    assert_debug_snapshot!(doc, @r#"
+    CommandDoc {
+        brief: RichText {
+            parts: [
+                Text(
+                    "List containers",
+                ),
+            ],
+        },
+        description: None,
+        usage: [
+            Usage {
+                parts: [
+                    Command(
+                        "docker",
+                    ),
+                    Command(
+                        "container",
+                    ),
+                    Command(
+                        "ls",
+                    ),
+                    Option(
+                        "[OPTIONS]",
+                    ),
+                ],
+            },
+        ],
+        arguments: [],
+        option_categories: [
+            CategoryDesc {
+                title: "Options",
+                options: [
+                    OptionDesc {
+                        short: Some(
+                            "-a",
+                        ),
+                        long: "--all",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Show all containers (default shows just running)",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-f",
+                        ),
+                        long: "--filter filter",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Filter output based on conditions provided",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--format string",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Format output using a custom template: 'table':            Print output in table format with column headers (default) 'table TEMPLATE':   Print output in table format using the given Go template 'json':             Print in JSON format 'TEMPLATE':         Print output using the given Go template. Refer to https://docs.docker.com/go/formatting/ for more information about formatting output with templates",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-n",
+                        ),
+                        long: "--last int",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Show n last created containers (includes all states) (default -1)",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-l",
+                        ),
+                        long: "--latest",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Show the latest created container (includes all states)",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--no-trunc",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Don't truncate output",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-q",
+                        ),
+                        long: "--quiet",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Only display container IDs",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-s",
+                        ),
+                        long: "--size",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Display total file sizes",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                ],
+            },
+        ],
+        groups: [],
+    }
+    "#);
+    assert!(commands.is_empty());
+}
+
+#[test]
+fn test_parse_cobra_kubectl_config_view() {
+    let text = "Display merged kubeconfig settings or a specified kubeconfig file.
+
+ You can use --output jsonpath={...} to extract specific values using a jsonpath expression.
+
+Examples:
+  # Show merged kubeconfig settings
+  kubectl config view
+  
+  # Show merged kubeconfig settings, raw certificate data, and exposed secrets
+  kubectl config view --raw
+  
+  # Get the password for the e2e user
+  kubectl config view -o jsonpath='{.users[?(@.name == \"e2e\")].user.password}'
+
+Options:
+    --allow-missing-template-keys=true:
+	If true, ignore any errors in templates when a field or map key is missing in the template. Only applies to golang and jsonpath output formats.
+
+    --flatten=false:
+	Flatten the resulting kubeconfig file into self-contained output (useful for creating portable kubeconfig files)
+
+    --merge=true:
+	Merge the full hierarchy of kubeconfig files
+
+    --minify=false:
+	Remove all information not used by current-context from the output
+
+    -o, --output='yaml':
+	Output format. One of: (json, yaml, name, go-template, go-template-file, template, templatefile, jsonpath, jsonpath-as-json, jsonpath-file).
+
+    --raw=false:
+	Display raw byte data and sensitive data
+
+    --show-managed-fields=false:
+	If true, keep the managedFields when printing objects in JSON or YAML format.
+
+    --template='':
+	Template string or path to template file to use when -o=go-template, -o=go-template-file. The template format is golang templates [http://golang.org/pkg/text/template/#pkg-overview].
+
+Usage:
+  kubectl config view [flags] [options]
+
+Use \"kubectl options\" for a list of global command-line options (applies to all commands).
+";
+    let (doc, commands) = parse_cobra(text);
+    assert_debug_snapshot!(doc, @r#"
+    CommandDoc {
+        brief: RichText {
+            parts: [
+                Text(
+                    "Display merged kubeconfig settings or a specified kubeconfig file.",
+                ),
+            ],
+        },
+        description: Some(
+            RichText {
+                parts: [
+                    Text(
+                        "You can use --output jsonpath={...} to extract specific values using a jsonpath expression.",
+                    ),
+                ],
+            },
+        ),
+        usage: [
+            Usage {
+                parts: [
+                    Command(
+                        "kubectl",
+                    ),
+                    Command(
+                        "config",
+                    ),
+                    Command(
+                        "view",
+                    ),
+                    Option(
+                        "[flags]",
+                    ),
+                    Option(
+                        "[options]",
+                    ),
+                ],
+            },
+        ],
+        arguments: [],
+        option_categories: [
+            CategoryDesc {
+                title: "Options",
+                options: [
+                    OptionDesc {
+                        short: None,
+                        long: "--allow-missing-template-keys=true",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "If true, ignore any errors in templates when a field or map key is missing in the template. Only applies to golang and jsonpath output formats.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--flatten=false",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Flatten the resulting kubeconfig file into self-contained output (useful for creating portable kubeconfig files)",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--merge=true",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Merge the full hierarchy of kubeconfig files",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--minify=false",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Remove all information not used by current-context from the output",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-o",
+                        ),
+                        long: "--output='yaml'",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Output format. One of: (json, yaml, name, go-template, go-template-file, template, templatefile, jsonpath, jsonpath-as-json, jsonpath-file).",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--raw=false",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Display raw byte data and sensitive data",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--show-managed-fields=false",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "If true, keep the managedFields when printing objects in JSON or YAML format.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--template=''",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Template string or path to template file to use when -o=go-template, -o=go-template-file. The template format is golang templates [http://golang.org/pkg/text/template/#pkg-overview].",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                ],
+            },
+        ],
+        groups: [],
+    }
+    "#);
+    assert!(commands.is_empty());
+}
@@ -0,0 +1,102 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Environment in which the documented program is run, so the extracted help
+/// does not depend on the terminal, locale or shell of the user
+#[derive(Default, Debug, Clone)]
+pub struct ProgramEnvironment {
+    /// Start with an empty environment (only `PATH` is kept)
+    pub clear: bool,
+    /// Variables removed from the environment
+    pub remove: Vec<String>,
+    /// Variables set in the environment; they take precedence over the options below
+    pub vars: Vec<(String, String)>,
+    /// Terminal width used for wrapping of the help (`COLUMNS`)
+    pub columns: Option<usize>,
+    /// Disable colors (`NO_COLOR=1`, `CLICOLOR=0`)
+    pub no_color: bool,
+    /// Use the "C" locale (`LANG=C`, `LC_ALL=C`)
+    pub c_locale: bool,
+    /// Working directory of the program; the current directory when not set
+    pub working_dir: Option<PathBuf>,
+}
+
+impl ProgramEnvironment {
+    pub(crate) fn apply(&self, command: &mut Command) {
+        if self.clear {
+            command.env_clear();
+            if let Some(path) = std::env::var_os("PATH") {
+                command.env("PATH", path);
+            }
+        }
+        for name in &self.remove {
+            command.env_remove(name);
+        }
+        if let Some(columns) = self.columns {
+            command.env("COLUMNS", columns.to_string());
+        }
+        if self.no_color {
+            command.env("NO_COLOR", "1").env("CLICOLOR", "0");
+        }
+        if self.c_locale {
+            command.env("LANG", "C").env("LC_ALL", "C");
+        }
+        for (name, value) in &self.vars {
+            command.env(name, value);
+        }
+        if let Some(dir) = &self.working_dir {
+            command.current_dir(dir);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extractor::process::run_command;
+
+    fn run_with(environment: &ProgramEnvironment, script: &str) -> String {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        environment.apply(&mut command);
+        String::from_utf8(run_command(&mut command, None).unwrap().stdout).unwrap()
+    }
+
+    #[test]
+    fn test_environment_variables() {
+        let environment = ProgramEnvironment {
+            clear: true,
+            vars: vec![("LANG".to_string(), "en_US.UTF-8".to_string())],
+            columns: Some(80),
+            no_color: true,
+            c_locale: true,
+            ..Default::default()
+        };
+        let output = run_with(
+            &environment,
+            "echo $COLUMNS $NO_COLOR $CLICOLOR $LANG $LC_ALL",
+        );
+        assert_eq!(output, "80 1 0 en_US.UTF-8 C\n");
+        let output = run_with(&environment, "env | cut -d= -f1");
+        let names: Vec<_> = output.lines().collect();
+        for name in ["PATH", "COLUMNS", "NO_COLOR", "CLICOLOR", "LANG", "LC_ALL"] {
+            assert!(names.contains(&name), "{name} is not set");
+        }
+        // Variables of the parent (set for the tests by cargo) are removed
+        assert!(!names.contains(&"CARGO_PKG_NAME"));
+        assert!(!names.contains(&"HOME"));
+    }
+
+    #[test]
+    fn test_working_dir() {
+        let dir = std::env::temp_dir().canonicalize().unwrap();
+        let environment = ProgramEnvironment {
+            working_dir: Some(dir.clone()),
+            ..Default::default()
+        };
+        assert_eq!(
+            run_with(&environment, "pwd -P"),
+            format!("{}\n", dir.display())
+        );
+    }
+}
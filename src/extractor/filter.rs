@@ -0,0 +1,126 @@
+/// Selection of subcommands that are extracted; commands that are not selected
+/// are never run, nor are their subcommands
+///
+/// Patterns are matched against paths of commands word by word (e.g. "remote add"),
+/// each word may contain `*` and `?` wildcards. A pattern also matches all
+/// subcommands of the matched command.
+#[derive(Default, Debug)]
+pub(crate) struct CommandFilter {
+    include: Vec<Vec<String>>,
+    exclude: Vec<Vec<String>>,
+    max_depth: Option<usize>,
+}
+
+fn split_pattern(pattern: &str) -> Vec<String> {
+    pattern.split_whitespace().map(str::to_string).collect()
+}
+
+/// Matches `text` against a pattern with `*` (any sequence) and `?` (any character)
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Position after the last `*` and the text position it is tried at
+    let mut backtrack = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p + 1, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            p = star_p;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Pattern matches the path or one of its ancestors
+fn matches_prefix(pattern: &[String], path: &[String]) -> bool {
+    pattern.len() <= path.len() && pattern.iter().zip(path).all(|(p, w)| glob_match(p, w))
+}
+
+/// Path is an ancestor of commands that may match the pattern
+fn is_ancestor_of_match(pattern: &[String], path: &[String]) -> bool {
+    pattern.len() > path.len() && pattern.iter().zip(path).all(|(p, w)| glob_match(p, w))
+}
+
+impl CommandFilter {
+    pub fn new(include: &[String], exclude: &[String], max_depth: Option<usize>) -> Self {
+        CommandFilter {
+            include: include.iter().map(|p| split_pattern(p)).collect(),
+            exclude: exclude.iter().map(|p| split_pattern(p)).collect(),
+            max_depth,
+        }
+    }
+
+    /// `path` contains names of the command and of its parents (without the program)
+    pub fn accepts(&self, path: &[String]) -> bool {
+        if self.max_depth.is_some_and(|depth| path.len() > depth) {
+            return false;
+        }
+        if self.exclude.iter().any(|p| matches_prefix(p, path)) {
+            return false;
+        }
+        self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|p| matches_prefix(p, path) || is_ancestor_of_match(p, path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(s: &str) -> Vec<String> {
+        split_pattern(s)
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*", "debug"));
+        assert!(glob_match("de*", "debug"));
+        assert!(glob_match("*bug", "debug"));
+        assert!(glob_match("d?b*g", "debug"));
+        assert!(glob_match("*e*u*", "debug"));
+        assert!(!glob_match("de", "debug"));
+        assert!(!glob_match("*x*", "debug"));
+        assert!(!glob_match("d?", "debug"));
+    }
+
+    #[test]
+    fn test_exclude() {
+        let filter =
+            CommandFilter::new(&[], &["debug *".to_string(), "internal".to_string()], None);
+        assert!(filter.accepts(&path("debug")));
+        assert!(!filter.accepts(&path("debug dump")));
+        assert!(!filter.accepts(&path("debug dump all")));
+        assert!(!filter.accepts(&path("internal")));
+        assert!(!filter.accepts(&path("internal x")));
+        assert!(filter.accepts(&path("remote")));
+    }
+
+    #[test]
+    fn test_include() {
+        let filter = CommandFilter::new(&["remote a*".to_string()], &[], None);
+        assert!(filter.accepts(&path("remote")));
+        assert!(filter.accepts(&path("remote add")));
+        assert!(filter.accepts(&path("remote add origin")));
+        assert!(!filter.accepts(&path("remote remove")));
+        assert!(!filter.accepts(&path("commit")));
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let filter = CommandFilter::new(&[], &[], Some(1));
+        assert!(filter.accepts(&path("remote")));
+        assert!(!filter.accepts(&path("remote add")));
+    }
+}
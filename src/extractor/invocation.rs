@@ -0,0 +1,110 @@
+use serde::Deserialize;
+use std::process::Output;
+
+/// How help of a command is requested
+#[derive(Debug, Clone)]
+pub enum HelpInvocation {
+    /// Flag appended after the command path, e.g. `prog sub --help`
+    Flag(String),
+    /// Subcommand put before the command path, e.g. `prog help sub`
+    Command(String),
+}
+
+impl Default for HelpInvocation {
+    fn default() -> Self {
+        HelpInvocation::Flag("--help".to_string())
+    }
+}
+
+impl HelpInvocation {
+    pub(crate) fn arguments(&self, path: &[String]) -> Vec<String> {
+        match self {
+            HelpInvocation::Flag(flag) => path.iter().chain([flag]).cloned().collect(),
+            HelpInvocation::Command(name) => [name].into_iter().chain(path).cloned().collect(),
+        }
+    }
+
+    /// Name of the subcommand printing help, which is not documented as a command
+    pub(crate) fn command_name(&self) -> &str {
+        match self {
+            HelpInvocation::Flag(_) => "help",
+            HelpInvocation::Command(name) => name,
+        }
+    }
+
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            HelpInvocation::Flag(flag) => format!("{flag} flag"),
+            HelpInvocation::Command(name) => format!("{name} command"),
+        }
+    }
+}
+
+/// Where the version of the program comes from
+#[derive(Debug, Clone)]
+pub enum VersionSource {
+    /// Output of the program called with the flag
+    Flag(String),
+    /// Given version string; the program is not called
+    Fixed(String),
+}
+
+impl Default for VersionSource {
+    fn default() -> Self {
+        VersionSource::Flag("--version".to_string())
+    }
+}
+
+/// Output stream of the program that contains help
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStream {
+    #[default]
+    Stdout,
+    Stderr,
+    /// Stdout, or stderr when nothing is printed to stdout
+    Auto,
+}
+
+impl OutputStream {
+    pub(crate) fn select(self, output: &Output) -> &[u8] {
+        match self {
+            OutputStream::Stdout => &output.stdout,
+            OutputStream::Stderr => &output.stderr,
+            OutputStream::Auto if output.stdout.trim_ascii().is_empty() => &output.stderr,
+            OutputStream::Auto => &output.stdout,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::ExitStatus;
+
+    #[test]
+    fn test_help_arguments() {
+        let path = vec!["remote".to_string(), "add".to_string()];
+        assert_eq!(
+            HelpInvocation::default().arguments(&path),
+            ["remote", "add", "--help"]
+        );
+        assert_eq!(
+            HelpInvocation::Command("help".to_string()).arguments(&path),
+            ["help", "remote", "add"]
+        );
+    }
+
+    #[test]
+    fn test_select_output() {
+        let output = |stdout: &str, stderr: &str| Output {
+            status: ExitStatus::from_raw(0),
+            stdout: stdout.as_bytes().to_vec(),
+            stderr: stderr.as_bytes().to_vec(),
+        };
+        assert_eq!(OutputStream::Auto.select(&output("out", "err")), b"out");
+        assert_eq!(OutputStream::Auto.select(&output("\n", "err")), b"err");
+        assert_eq!(OutputStream::Stderr.select(&output("out", "err")), b"err");
+    }
+}
@@ -1,42 +1,130 @@
-/*use anyhow::bail;
-use crate::commands::{CommandDoc, CommandOuterDoc};
+use crate::commands::{
+    ArgumentDesc, CategoryDesc, CommandDoc, CommandOuterDoc, OptionDesc, ValueInfo,
+};
+use crate::extractor::parser::HelpParser;
 use crate::extractor::sections::Section;
+use crate::extractor::usage::{parse_grouped_usage, split_short_long};
 use crate::text::RichText;
+use anyhow::bail;
 
 pub(crate) struct ManParser {}
 
 impl ManParser {
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         Self {}
     }
+}
+
+impl HelpParser for ManParser {
+    fn name(&self) -> &'static str {
+        "man"
+    }
+
+    fn detect(&self, sections: &[Section]) -> bool {
+        find_section(sections, "NAME").is_some() && find_section(sections, "SYNOPSIS").is_some()
+    }
 
-    pub fn parse(
+    fn parse(
         &self,
         sections: &mut [Section],
     ) -> anyhow::Result<(CommandDoc, Vec<CommandOuterDoc>)> {
-        let Some(section) = sections.get(1) else {
-            bail!("2nd section not found");
+        let Some(name_section) = find_section(sections, "NAME") else {
+            bail!("NAME section not found");
         };
-        if section.first_line() != "NAME" {
-            bail!("2nd section is not NAME")
-        }
-        let brief = if let Some((left, _right)) = section.subsections().first().and_then(|s| s.first_line().split_once(": ")) {
-            RichText::from_single_line(left)
+        let brief = if let Some((_name, brief)) = name_section
+            .flatten_child_lines()
+            .next()
+            .and_then(|line| line.split_once(" - "))
+        {
+            RichText::from_single_line(brief.trim())
         } else {
             bail!("Invalid NAME format")
         };
 
-        Ok(
-            (CommandDoc {
+        let usage = find_section(sections, "SYNOPSIS")
+            .map(|s| {
+                s.subsections()
+                    .iter()
+                    .map(|s| {
+                        let mut lines = s.paragraph();
+                        for line in s.flatten_child_lines() {
+                            lines.push(' ');
+                            lines.push_str(line);
+                        }
+                        parse_grouped_usage(&lines)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let description = find_section(sections, "DESCRIPTION").and_then(|s| {
+            let mut desc = RichText::new();
+            for s in s.subsections() {
+                desc.add_lines(s.lines());
+                // Deeper indented blocks are usually list items with hanging indents
+                for item in s.subsections() {
+                    let mut lines = Vec::new();
+                    collect_lines(item, &mut lines);
+                    desc.add_lines(&lines);
+                }
+            }
+            if desc.is_empty() { None } else { Some(desc) }
+        });
+
+        let mut arguments = Vec::new();
+        let mut options = Vec::new();
+        if let Some(section) = find_section(sections, "OPTIONS") {
+            for s in section.subsections() {
+                let (brief, description) = s.subsections_as_brief_and_full_description();
+                let names: Vec<&str> = s.lines().iter().flat_map(|l| l.split(", ")).collect();
+                if names.first().is_some_and(|n| n.starts_with('<')) {
+                    arguments.push(ArgumentDesc {
+                        name: names.join(", "),
+                        brief,
+                        description,
+                        info: ValueInfo::default(),
+                    });
+                    continue;
+                }
+                let (short, long) = split_short_long(&names);
+                options.push(OptionDesc {
+                    short,
+                    long,
+                    brief,
+                    description,
+                    info: ValueInfo::default(),
+                })
+            }
+        }
+
+        Ok((
+            CommandDoc {
                 brief,
-                description: None,
-                usage: vec![],
-                arguments: vec![],
-                option_categories: vec![],
-            }, Vec::new()
-            )
-        )
-        todo!()
+                description,
+                usage,
+                arguments,
+                option_categories: if options.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![CategoryDesc {
+                        title: "Options".to_string(),
+                        options,
+                    }]
+                },
+                groups: Vec::new(),
+            },
+            Vec::new(),
+        ))
+    }
+}
+
+fn find_section<'a, 'b>(sections: &'b [Section<'a>], title: &str) -> Option<&'b Section<'a>> {
+    sections.iter().find(|s| s.first_line() == title)
+}
+
+fn collect_lines<'a>(section: &Section<'a>, out: &mut Vec<&'a str>) {
+    out.extend_from_slice(section.lines());
+    for s in section.subsections() {
+        collect_lines(s, out);
     }
 }
-*/
@@ -1,8 +1,8 @@
-/*use insta::assert_debug_snapshot;
 use crate::commands::{CommandDoc, CommandOuterDoc};
-use crate::extractor::clap_parser::ClapParser;
 use crate::extractor::man_parser::ManParser;
+use crate::extractor::parser::HelpParser;
 use crate::extractor::sections::parse_sections;
+use insta::assert_debug_snapshot;
 
 fn parse_man(text: &str) -> (CommandDoc, Vec<CommandOuterDoc>) {
     let mut sections = parse_sections(text);
@@ -10,7 +10,7 @@ fn parse_man(text: &str) -> (CommandDoc, Vec<CommandOuterDoc>) {
 }
 
 #[test]
-fn test_parse_clap_cargo_add_usage() {
+fn test_parse_man_git_commit() {
     let text = r#"GIT-COMMIT(1)                                                                        Git Manual                                                                       GIT-COMMIT(1)
 
 NAME
@@ -451,7 +451,1103 @@ Git 2.43.0
         "#;
 
     let (doc, commands) = parse_man(text);
-    assert_debug_snapshot!(doc, @r#"        "#);
+    assert_debug_snapshot!(doc, @r##"
+    CommandDoc {
+        brief: RichText {
+            parts: [
+                Text(
+                    "Record changes to the repository",
+                ),
+            ],
+        },
+        description: Some(
+            RichText {
+                parts: [
+                    Text(
+                        "Create a new commit containing the current contents of the index and the given log message describing the changes. The new commit is a direct child of HEAD, usually the tip of the current branch, and the branch is updated to point to it (unless no branch is associated with the working tree, in which case HEAD is \"detached\" as described in git- checkout(1)).",
+                    ),
+                    Text(
+                        "The content to be committed can be specified in several ways:",
+                    ),
+                    Text(
+                        "1. by using git-add(1) to incrementally \"add\" changes to the index before using the commit command (Note: even modified files must be \"added\");",
+                    ),
+                    Text(
+                        "2. by using git-rm(1) to remove files from the working tree and the index, again before using the commit command;",
+                    ),
+                    Text(
+                        "3. by listing files as arguments to the commit command (without --interactive or --patch switch), in which case the commit will ignore changes staged in the index, and instead record the current content of the listed files (which must already be known to Git);",
+                    ),
+                    Text(
+                        "4. by using the -a switch with the commit command to automatically \"add\" changes from all known files (i.e. all files that are already listed in the index) and to automatically \"rm\" files in the index that have been removed from the working tree, and then perform the actual commit;",
+                    ),
+                    Text(
+                        "5. by using the --interactive or --patch switches with the commit command to decide one by one which files or hunks should be part of the commit in addition to contents in the index, before finalizing the operation. See the “Interactive Mode” section of git-add(1) to learn how to operate these modes.",
+                    ),
+                    Text(
+                        "The --dry-run option can be used to obtain a summary of what is included by any of the above for the next commit by giving the same set of parameters (options and paths).",
+                    ),
+                    Text(
+                        "If you make a commit and then find a mistake immediately after that, you can recover from it with git reset.",
+                    ),
+                ],
+            },
+        ),
+        usage: [
+            Usage {
+                parts: [
+                    Command(
+                        "git",
+                    ),
+                    Command(
+                        "commit",
+                    ),
+                    Option(
+                        "[-a | --interactive | --patch]",
+                    ),
+                    Option(
+                        "[-s]",
+                    ),
+                    Option(
+                        "[-v]",
+                    ),
+                    Option(
+                        "[-u<mode>]",
+                    ),
+                    Option(
+                        "[--amend]",
+                    ),
+                    Option(
+                        "[--dry-run]",
+                    ),
+                    Option(
+                        "[(-c | -C | --squash) <commit> | --fixup [(amend|reword):]<commit>)]",
+                    ),
+                    Option(
+                        "[-F <file> | -m <msg>]",
+                    ),
+                    Option(
+                        "[--reset-author]",
+                    ),
+                    Option(
+                        "[--allow-empty]",
+                    ),
+                    Option(
+                        "[--allow-empty-message]",
+                    ),
+                    Option(
+                        "[--no-verify]",
+                    ),
+                    Option(
+                        "[-e]",
+                    ),
+                    Option(
+                        "[--author=<author>]",
+                    ),
+                    Option(
+                        "[--date=<date>]",
+                    ),
+                    Option(
+                        "[--cleanup=<mode>]",
+                    ),
+                    Option(
+                        "[--[no-]status]",
+                    ),
+                    Option(
+                        "[-i | -o]",
+                    ),
+                    Option(
+                        "[--pathspec-from-file=<file> [--pathspec-file-nul]]",
+                    ),
+                    Option(
+                        "[(--trailer <token>[(=|:)<value>])...]",
+                    ),
+                    Option(
+                        "[-S[<keyid>]]",
+                    ),
+                    Option(
+                        "[--]",
+                    ),
+                    Option(
+                        "[<pathspec>...]",
+                    ),
+                ],
+            },
+        ],
+        arguments: [
+            ArgumentDesc {
+                name: "<pathspec>...",
+                brief: RichText {
+                    parts: [
+                        Text(
+                            "When pathspec is given on the command line, commit the contents of the files that match the pathspec without recording the changes already added to the index. The contents of these files are also staged for the next commit on top of what have been staged before.",
+                        ),
+                    ],
+                },
+                description: Some(
+                    RichText {
+                        parts: [
+                            Text(
+                                "For more details, see the pathspec entry in gitglossary(7).",
+                            ),
+                        ],
+                    },
+                ),
+                info: ValueInfo {
+                    value_name: None,
+                    default: None,
+                    possible_values: [],
+                    env: None,
+                    aliases: [],
+                    value_hint: None,
+                },
+            },
+        ],
+        option_categories: [
+            CategoryDesc {
+                title: "Options",
+                options: [
+                    OptionDesc {
+                        short: Some(
+                            "-a",
+                        ),
+                        long: "--all",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Tell the command to automatically stage files that have been modified and deleted, but new files you have not told Git about are not affected.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-p",
+                        ),
+                        long: "--patch",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Use the interactive patch selection interface to choose which changes to commit. See git-add(1) for details.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-C <commit>",
+                        ),
+                        long: "--reuse-message=<commit>",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Take an existing commit object, and reuse the log message and the authorship information (including the timestamp) when creating the commit.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-c <commit>",
+                        ),
+                        long: "--reedit-message=<commit>",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Like -C, but with -c the editor is invoked, so that the user can further edit the commit message.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--fixup=[(amend|reword):]<commit>",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Create a new commit which \"fixes up\" <commit> when applied with git rebase --autosquash. Plain --fixup=<commit> creates a \"fixup!\" commit which changes the content of <commit> but leaves its log message untouched.  --fixup=amend:<commit> is similar but creates an \"amend!\" commit which also replaces the log message of <commit> with the log message of the \"amend!\" commit.  --fixup=reword:<commit> creates an \"amend!\" commit which replaces the log message of <commit> with its own log message but makes no changes to the content of <commit>.",
+                                ),
+                            ],
+                        },
+                        description: Some(
+                            RichText {
+                                parts: [
+                                    Text(
+                                        "The commit created by plain --fixup=<commit> has a subject composed of \"fixup!\" followed by the subject line from <commit>, and is recognized specially by git rebase --autosquash. The -m option may be used to supplement the log message of the created commit, but the additional commentary will be thrown away once the \"fixup!\" commit is squashed into <commit> by git rebase --autosquash.",
+                                    ),
+                                    Text(
+                                        "The commit created by --fixup=amend:<commit> is similar but its subject is instead prefixed with \"amend!\". The log message of <commit> is copied into the log message of the \"amend!\" commit and opened in an editor so it can be refined. When git rebase --autosquash squashes the \"amend!\" commit into <commit>, the log message of <commit> is replaced by the refined log message from the \"amend!\" commit. It is an error for the \"amend!\" commit’s log message to be empty unless --allow-empty-message is specified.",
+                                    ),
+                                    Text(
+                                        "--fixup=reword:<commit> is shorthand for --fixup=amend:<commit> --only. It creates an \"amend!\" commit with only a log message (ignoring any changes staged in the index). When squashed by git rebase --autosquash, it replaces the log message of <commit> without making any other changes.",
+                                    ),
+                                    Text(
+                                        "Neither \"fixup!\" nor \"amend!\" commits change authorship of <commit> when applied by git rebase --autosquash. See git-rebase(1) for details.",
+                                    ),
+                                ],
+                            },
+                        ),
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--squash=<commit>",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Construct a commit message for use with rebase --autosquash. The commit message subject line is taken from the specified commit with a prefix of \"squash! \". Can be used with additional commit message options (-m/-c/-C/-F). See git-rebase(1) for details.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--reset-author",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "When used with -C/-c/--amend options, or when committing after a conflicting cherry-pick, declare that the authorship of the resulting commit now belongs to the committer. This also renews the author timestamp.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--short",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "When doing a dry-run, give the output in the short-format. See git-status(1) for details. Implies --dry-run.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--branch",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Show the branch and tracking info even in short-format.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--porcelain",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "When doing a dry-run, give the output in a porcelain-ready format. See git-status(1) for details. Implies --dry-run.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--long",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "When doing a dry-run, give the output in the long-format. Implies --dry-run.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-z",
+                        ),
+                        long: "--null",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "When showing short or porcelain status output, print the filename verbatim and terminate the entries with NUL, instead of LF. If no format is given, implies the --porcelain output format. Without the -z option, filenames with \"unusual\" characters are quoted as explained for the configuration variable core.quotePath (see git- config(1)).",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-F <file>",
+                        ),
+                        long: "--file=<file>",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Take the commit message from the given file. Use - to read the message from the standard input.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--author=<author>",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Override the commit author. Specify an explicit author using the standard A U Thor <author@example.com> format. Otherwise <author> is assumed to be a pattern and is used to search for an existing commit by that author (i.e. rev-list --all -i --author=<author>); the commit author is then copied from the first such commit found.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--date=<date>",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Override the author date used in the commit.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-m <msg>",
+                        ),
+                        long: "--message=<msg>",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Use the given <msg> as the commit message. If multiple -m options are given, their values are concatenated as separate paragraphs.",
+                                ),
+                            ],
+                        },
+                        description: Some(
+                            RichText {
+                                parts: [
+                                    Text(
+                                        "The -m option is mutually exclusive with -c, -C, and -F.",
+                                    ),
+                                ],
+                            },
+                        ),
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-t <file>",
+                        ),
+                        long: "--template=<file>",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "When editing the commit message, start the editor with the contents in the given file. The commit.template configuration variable is often used to give this option implicitly to the command. This mechanism can be used by projects that want to guide participants with some hints on what to write in the message in what order. If the user exits the editor without editing the message, the commit is aborted. This has no effect when a message is given by other means, e.g. with the -m or -F options.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-s",
+                        ),
+                        long: "--signoff, --no-signoff",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Add a Signed-off-by trailer by the committer at the end of the commit log message. The meaning of a signoff depends on the project to which you’re committing. For example, it may certify that the committer has the rights to submit the work under the project’s license or agrees to some contributor representation, such as a Developer Certificate of Origin. (See http://developercertificate.org for the one used by the Linux kernel and Git projects.) Consult the documentation or leadership of the project to which you’re contributing to understand how the signoffs are used in that project.",
+                                ),
+                            ],
+                        },
+                        description: Some(
+                            RichText {
+                                parts: [
+                                    Text(
+                                        "The --no-signoff option can be used to countermand an earlier --signoff option on the command line.",
+                                    ),
+                                ],
+                            },
+                        ),
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--trailer <token>[(=|:)<value>]",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Specify a (<token>, <value>) pair that should be applied as a trailer. (e.g.  git commit --trailer \"Signed-off-by:C O Mitter \\ <committer@example.com>\" --trailer \"Helped-by:C O Mitter \\ <committer@example.com>\" will add the \"Signed-off-by\" trailer and the \"Helped-by\" trailer to the commit message.) The trailer.*  configuration variables (git-interpret-trailers(1)) can be used to define if a duplicated trailer is omitted, where in the run of trailers each trailer would appear, and other details.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-n",
+                        ),
+                        long: "--[no-]verify",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "By default, the pre-commit and commit-msg hooks are run. When any of --no-verify or -n is given, these are bypassed. See also githooks(5).",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--allow-empty",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Usually recording a commit that has the exact same tree as its sole parent commit is a mistake, and the command prevents you from making such a commit. This option bypasses the safety, and is primarily for use by foreign SCM interface scripts.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--allow-empty-message",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Like --allow-empty this command is primarily for use by foreign SCM interface scripts. It allows you to create a commit with an empty commit message without using plumbing commands like git-commit-tree(1).",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--cleanup=<mode>",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "This option determines how the supplied commit message should be cleaned up before committing. The <mode> can be strip, whitespace, verbatim, scissors or default.",
+                                ),
+                            ],
+                        },
+                        description: Some(
+                            RichText {
+                                parts: [
+                                    Text(
+                                        "strip",
+                                    ),
+                                    Text(
+                                        "Strip leading and trailing empty lines, trailing whitespace, commentary and collapse consecutive empty lines.",
+                                    ),
+                                    Text(
+                                        "whitespace",
+                                    ),
+                                    Text(
+                                        "Same as strip except #commentary is not removed.",
+                                    ),
+                                    Text(
+                                        "verbatim",
+                                    ),
+                                    Text(
+                                        "Do not change the message at all.",
+                                    ),
+                                    Text(
+                                        "scissors",
+                                    ),
+                                    Text(
+                                        "Same as whitespace except that everything from (and including) the line found below is truncated, if the message is to be edited. \"\" can be customized with core.commentChar.",
+                                    ),
+                                    Text(
+                                        "# ------------------------ >8 ------------------------",
+                                    ),
+                                    Text(
+                                        "default",
+                                    ),
+                                    Text(
+                                        "Same as strip if the message is to be edited. Otherwise whitespace.",
+                                    ),
+                                    Text(
+                                        "The default can be changed by the commit.cleanup configuration variable (see git-config(1)).",
+                                    ),
+                                ],
+                            },
+                        ),
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-e",
+                        ),
+                        long: "--edit",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "The message taken from file with -F, command line with -m, and from commit object with -C are usually used as the commit log message unmodified. This option lets you further edit the message taken from these sources.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--no-edit",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Use the selected commit message without launching an editor. For example, git commit --amend --no-edit amends a commit without changing its commit message.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--amend",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Replace the tip of the current branch by creating a new commit. The recorded tree is prepared as usual (including the effect of the -i and -o options and explicit pathspec), and the message from the original commit is used as the starting point, instead of an empty message, when no other message is specified from the command line via options such as -m, -F, -c, etc. The new commit has the same parents and author as the current one (the --reset-author option can countermand this).",
+                                ),
+                            ],
+                        },
+                        description: Some(
+                            RichText {
+                                parts: [
+                                    Text(
+                                        "It is a rough equivalent for:",
+                                    ),
+                                    Text(
+                                        "$ git reset --soft HEAD^ $ ... do something else to come up with the right tree ... $ git commit -c ORIG_HEAD",
+                                    ),
+                                    Text(
+                                        "but can be used to amend a merge commit.",
+                                    ),
+                                    Text(
+                                        "You should understand the implications of rewriting history if you amend a commit that has already been published. (See the \"RECOVERING FROM UPSTREAM REBASE\" section in git-rebase(1).)",
+                                    ),
+                                ],
+                            },
+                        ),
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--no-post-rewrite",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Bypass the post-rewrite hook.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-i",
+                        ),
+                        long: "--include",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Before making a commit out of staged contents so far, stage the contents of paths given on the command line as well. This is usually not what you want unless you are concluding a conflicted merge.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-o",
+                        ),
+                        long: "--only",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Make a commit by taking the updated working tree contents of the paths specified on the command line, disregarding any contents that have been staged for other paths. This is the default mode of operation of git commit if any paths are given on the command line, in which case this option can be omitted. If this option is specified together with --amend, then no paths need to be specified, which can be used to amend the last commit without committing changes that have already been staged. If used together with --allow-empty paths are also not required, and an empty commit will be created.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--pathspec-from-file=<file>",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Pathspec is passed in <file> instead of commandline args. If <file> is exactly - then standard input is used. Pathspec elements are separated by LF or CR/LF. Pathspec elements can be quoted as explained for the configuration variable core.quotePath (see git-config(1)). See also --pathspec-file-nul and global --literal-pathspecs.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--pathspec-file-nul",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Only meaningful with --pathspec-from-file. Pathspec elements are separated with NUL character and all other characters are taken literally (including newlines and quotes).",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-u[<mode>]",
+                        ),
+                        long: "--untracked-files[=<mode>]",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Show untracked files.",
+                                ),
+                            ],
+                        },
+                        description: Some(
+                            RichText {
+                                parts: [
+                                    Text(
+                                        "The mode parameter is optional (defaults to all), and is used to specify the handling of untracked files; when -u is not used, the default is normal, i.e. show untracked files and directories.",
+                                    ),
+                                    Text(
+                                        "The possible options are:",
+                                    ),
+                                    Text(
+                                        "•   no - Show no untracked files",
+                                    ),
+                                    Text(
+                                        "•   normal - Shows untracked files and directories",
+                                    ),
+                                    Text(
+                                        "•   all - Also shows individual files in untracked directories.",
+                                    ),
+                                    Text(
+                                        "The default can be changed using the status.showUntrackedFiles configuration variable documented in git-config(1).",
+                                    ),
+                                ],
+                            },
+                        ),
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-v",
+                        ),
+                        long: "--verbose",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Show unified diff between the HEAD commit and what would be committed at the bottom of the commit message template to help the user describe the commit by reminding what changes the commit has. Note that this diff output doesn’t have its lines prefixed with #. This diff will not be a part of the commit message. See the commit.verbose configuration variable in git-config(1).",
+                                ),
+                            ],
+                        },
+                        description: Some(
+                            RichText {
+                                parts: [
+                                    Text(
+                                        "If specified twice, show in addition the unified diff between what would be committed and the worktree files, i.e. the unstaged changes to tracked files.",
+                                    ),
+                                ],
+                            },
+                        ),
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-q",
+                        ),
+                        long: "--quiet",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Suppress commit summary message.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--dry-run",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Do not create a commit, but show a list of paths that are to be committed, paths with local changes that will be left uncommitted and paths that are untracked.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--status",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Include the output of git-status(1) in the commit message template when using an editor to prepare the commit message. Defaults to on, but can be used to override configuration variable commit.status.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--no-status",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Do not include the output of git-status(1) in the commit message template when using an editor to prepare the default commit message.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: Some(
+                            "-S[<keyid>]",
+                        ),
+                        long: "--gpg-sign[=<keyid>], --no-gpg-sign",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "GPG-sign commits. The keyid argument is optional and defaults to the committer identity; if specified, it must be stuck to the option without a space.  --no-gpg-sign is useful to countermand both commit.gpgSign configuration variable, and earlier --gpg-sign.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                    OptionDesc {
+                        short: None,
+                        long: "--",
+                        brief: RichText {
+                            parts: [
+                                Text(
+                                    "Do not interpret any more arguments as options.",
+                                ),
+                            ],
+                        },
+                        description: None,
+                        info: ValueInfo {
+                            value_name: None,
+                            default: None,
+                            possible_values: [],
+                            env: None,
+                            aliases: [],
+                            value_hint: None,
+                        },
+                    },
+                ],
+            },
+        ],
+        groups: [],
+    }
+    "##);
     assert!(commands.is_empty());
 }
-*/
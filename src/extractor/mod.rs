@@ -1,9 +1,27 @@
-mod clap_parser;
-mod man_parser;
+mod ansi;
+mod argparse_parser;
+pub(crate) mod capture;
+pub(crate) mod clap_parser;
+mod click_parser;
+mod cobra_parser;
+pub(crate) mod environment;
+mod filter;
+pub(crate) mod invocation;
+pub(crate) mod man_parser;
+pub(crate) mod parser;
+mod process;
 pub(crate) mod runner;
 pub(crate) mod sections;
+mod usage;
+pub(crate) mod value_info;
 
+#[cfg(test)]
+mod argparse_parser_tests;
 #[cfg(test)]
 mod clap_parser_tests;
 #[cfg(test)]
+mod click_parser_tests;
+#[cfg(test)]
+mod cobra_parser_tests;
+#[cfg(test)]
 mod man_parser_tests;
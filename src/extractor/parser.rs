@@ -0,0 +1,167 @@
+use crate::commands::{CommandDoc, CommandOuterDoc};
+use crate::extractor::argparse_parser::ArgparseParser;
+use crate::extractor::clap_parser::ClapParser;
+use crate::extractor::click_parser::ClickParser;
+use crate::extractor::cobra_parser::CobraParser;
+use crate::extractor::man_parser::ManParser;
+use crate::extractor::sections::Section;
+use anyhow::bail;
+use serde::Deserialize;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Parser of a single help output
+///
+/// Besides the built-in parsers (see `BuiltinParser`), a parser for another format can be
+/// implemented and passed to `DocBuilder::parser`.
+pub trait HelpParser: Send + Sync {
+    /// Name used to select the parser explicitly (e.g. `--parser clap`)
+    fn name(&self) -> &'static str;
+
+    /// Returns true if the help output looks like the format of this parser
+    fn detect(&self, sections: &[Section]) -> bool;
+
+    /// Returns the documentation of the command and the subcommands listed in its help
+    fn parse(&self, sections: &mut [Section])
+    -> anyhow::Result<(CommandDoc, Vec<CommandOuterDoc>)>;
+}
+
+static COBRA: CobraParser = CobraParser::new();
+static CLICK: ClickParser = ClickParser::new();
+static CLAP: ClapParser = ClapParser::new();
+static ARGPARSE: ArgparseParser = ArgparseParser::new();
+static MAN: ManParser = ManParser::new();
+
+/// All known parsers; the order is used during the detection
+static PARSERS: &[&dyn HelpParser] = &[
+    // Cobra has to be before clap, as its "Usage:  prog" variant also matches clap
+    &COBRA,
+    // Click help starts with "Usage: " as well, it's recognized by the indented description
+    &CLICK, &CLAP, &ARGPARSE, &MAN,
+];
+
+/// Parsers of help formats shipped with cli_doc
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BuiltinParser {
+    /// Rust `clap`
+    Clap,
+    /// Python `click`
+    Click,
+    /// Python `argparse`
+    Argparse,
+    /// Go `cobra`
+    Cobra,
+    /// Help printed as a man page
+    Man,
+}
+
+impl BuiltinParser {
+    fn parser(&self) -> &'static dyn HelpParser {
+        match self {
+            BuiltinParser::Clap => &CLAP,
+            BuiltinParser::Click => &CLICK,
+            BuiltinParser::Argparse => &ARGPARSE,
+            BuiltinParser::Cobra => &COBRA,
+            BuiltinParser::Man => &MAN,
+        }
+    }
+}
+
+impl HelpParser for BuiltinParser {
+    fn name(&self) -> &'static str {
+        self.parser().name()
+    }
+
+    fn detect(&self, sections: &[Section]) -> bool {
+        self.parser().detect(sections)
+    }
+
+    fn parse(
+        &self,
+        sections: &mut [Section],
+    ) -> anyhow::Result<(CommandDoc, Vec<CommandOuterDoc>)> {
+        self.parser().parse(sections)
+    }
+}
+
+impl From<BuiltinParser> for Arc<dyn HelpParser> {
+    fn from(parser: BuiltinParser) -> Self {
+        Arc::new(parser)
+    }
+}
+
+impl FromStr for BuiltinParser {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> anyhow::Result<Self> {
+        let parsers = [
+            BuiltinParser::Clap,
+            BuiltinParser::Click,
+            BuiltinParser::Argparse,
+            BuiltinParser::Cobra,
+            BuiltinParser::Man,
+        ];
+        match parsers.into_iter().find(|p| p.name() == name) {
+            Some(parser) => Ok(parser),
+            None => bail!(
+                "Unknown parser '{}', available parsers: {}",
+                name,
+                parsers.map(|p| p.name()).join(", ")
+            ),
+        }
+    }
+}
+
+pub fn detect_parser(sections: &[Section]) -> anyhow::Result<&'static dyn HelpParser> {
+    if let Some(parser) = PARSERS.iter().find(|p| p.detect(sections)) {
+        Ok(*parser)
+    } else {
+        bail!("Unknown format of help output")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extractor::sections::parse_sections;
+
+    #[test]
+    fn test_detect_parser() {
+        let clap = "Some program\n\nUsage: prog [OPTIONS]\n\nOptions:\n  -h, --help  Print help\n";
+        assert_eq!(detect_parser(&parse_sections(clap)).unwrap().name(), "clap");
+
+        let man = "PROG(1)\n\nNAME\n       prog - Some program\n\nSYNOPSIS\n       prog [-h]\n";
+        assert_eq!(detect_parser(&parse_sections(man)).unwrap().name(), "man");
+
+        let argparse =
+            "usage: prog [-h]\n\noptions:\n  -h, --help  show this help message and exit\n";
+        assert_eq!(
+            detect_parser(&parse_sections(argparse)).unwrap().name(),
+            "argparse"
+        );
+
+        let cobra =
+            "Some program\n\nUsage:\n  prog [flags]\n\nFlags:\n  -h, --help   help for prog\n";
+        assert_eq!(
+            detect_parser(&parse_sections(cobra)).unwrap().name(),
+            "cobra"
+        );
+
+        let click = "Usage: prog [OPTIONS]\n\n  Some program\n\nOptions:\n  --help  Show this message and exit.\n";
+        assert_eq!(
+            detect_parser(&parse_sections(click)).unwrap().name(),
+            "click"
+        );
+
+        assert!(detect_parser(&parse_sections("Hello world\n")).is_err());
+    }
+
+    #[test]
+    fn test_builtin_parser() {
+        let parser: BuiltinParser = "man".parse().unwrap();
+        assert_eq!(parser, BuiltinParser::Man);
+        assert_eq!(parser.name(), "man");
+        assert!("xyz".parse::<BuiltinParser>().is_err());
+    }
+}
@@ -0,0 +1,244 @@
+use anyhow::bail;
+use std::io::Read;
+use std::process::{Child, Command, ExitStatus, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often a running child is checked when a timeout is set
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+fn read_in_background(
+    stream: Option<impl Read + Send + 'static>,
+) -> thread::JoinHandle<std::io::Result<Vec<u8>>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut stream) = stream {
+            stream.read_to_end(&mut buffer)?;
+        }
+        Ok(buffer)
+    })
+}
+
+/// Kills the process group led by the child, i.e. also the processes it started
+/// unless they created their own group
+fn kill_process_group(child: &mut Child) {
+    #[cfg(unix)]
+    // SAFETY: kill() only sends a signal, a negative pid addresses the process group
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+    #[cfg(not(unix))]
+    let _ = child.kill();
+}
+
+/// Running children are in their own process groups, so a signal sent by the terminal
+/// (e.g. Ctrl-C) does not reach them; their groups are killed by a handler instead
+#[cfg(unix)]
+mod interrupt {
+    use std::sync::Once;
+    use std::sync::atomic::{AtomicI32, Ordering};
+
+    /// Process groups of running children, 0 marks a free slot; the handler cannot take
+    /// a lock, so a fixed array is used
+    static GROUPS: [AtomicI32; 256] = [const { AtomicI32::new(0) }; 256];
+    static INSTALL: Once = Once::new();
+    const SIGNALS: [libc::c_int; 3] = [libc::SIGINT, libc::SIGTERM, libc::SIGHUP];
+
+    extern "C" fn kill_groups(signal: libc::c_int) {
+        for group in &GROUPS {
+            let pgid = group.load(Ordering::SeqCst);
+            if pgid != 0 {
+                // SAFETY: kill(), signal() and raise() are async-signal-safe
+                unsafe {
+                    libc::kill(-pgid, libc::SIGKILL);
+                }
+            }
+        }
+        // The signal is delivered again with the default action, i.e. cli_doc terminates
+        // SAFETY: as above
+        unsafe {
+            libc::signal(signal, libc::SIG_DFL);
+            libc::raise(signal);
+        }
+    }
+
+    /// Installs the handler for signals with the default action; handlers of the
+    /// application using the library and ignored signals (e.g. by `nohup`) are kept
+    fn install() {
+        for signal in SIGNALS {
+            // SAFETY: sigaction() is called with valid pointers to initialized structs
+            unsafe {
+                let mut previous: libc::sigaction = std::mem::zeroed();
+                if libc::sigaction(signal, std::ptr::null(), &mut previous) != 0
+                    || previous.sa_sigaction != libc::SIG_DFL
+                {
+                    continue;
+                }
+                let mut action: libc::sigaction = std::mem::zeroed();
+                action.sa_sigaction = kill_groups as extern "C" fn(libc::c_int) as usize;
+                libc::sigemptyset(&mut action.sa_mask);
+                libc::sigaction(signal, &action, std::ptr::null_mut());
+            }
+        }
+    }
+
+    /// Process group killed on a signal until the guard is dropped
+    pub(super) struct Registration(Option<&'static AtomicI32>);
+
+    impl Drop for Registration {
+        fn drop(&mut self) {
+            if let Some(slot) = self.0 {
+                slot.store(0, Ordering::SeqCst);
+            }
+        }
+    }
+
+    /// When all slots are taken, the group is not killed on a signal
+    pub(super) fn register(pgid: u32) -> Registration {
+        INSTALL.call_once(install);
+        Registration(GROUPS.iter().find(|slot| {
+            slot.compare_exchange(0, pgid as i32, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+        }))
+    }
+}
+
+fn wait_with_timeout(child: &mut Child, timeout: Option<Duration>) -> anyhow::Result<ExitStatus> {
+    let Some(timeout) = timeout else {
+        return Ok(child.wait()?);
+    };
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        if Instant::now() >= deadline {
+            // The child may be already finished, so errors of kill are ignored
+            kill_process_group(child);
+            let _ = child.wait();
+            bail!("Process timed out after {} s", timeout.as_secs_f64());
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Joins a reader thread; a grandchild of the program (e.g. a daemon started by it) may
+/// keep the pipe open, so the process group is killed after the deadline, which closes
+/// the pipe and lets the thread finish
+fn join_reader(
+    reader: thread::JoinHandle<std::io::Result<Vec<u8>>>,
+    child: &mut Child,
+    start: Instant,
+    timeout: Option<Duration>,
+) -> anyhow::Result<Vec<u8>> {
+    if let Some(timeout) = timeout {
+        while !reader.is_finished() {
+            if start.elapsed() >= timeout {
+                kill_process_group(child);
+                bail!(
+                    "Process timed out after {} s, its output was not closed",
+                    timeout.as_secs_f64()
+                );
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+    Ok(reader.join().unwrap()?)
+}
+
+/// Like `Command::output()`, but stdin is closed and the child is killed together with
+/// the processes it started when it does not finish in time
+pub(crate) fn run_command(
+    command: &mut Command,
+    timeout: Option<Duration>,
+) -> anyhow::Result<Output> {
+    let start = Instant::now();
+    // The child leads a new process group, so its descendants can be killed with it
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(command, 0);
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    #[cfg(unix)]
+    let _registration = interrupt::register(child.id());
+    // Pipes are read in separate threads, so a child filling one of them cannot block
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+    let status = wait_with_timeout(&mut child, timeout)?;
+    Ok(Output {
+        status,
+        stdout: join_reader(stdout, &mut child, start, timeout)?,
+        stderr: join_reader(stderr, &mut child, start, timeout)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_command() {
+        let output = run_command(Command::new("sh").args(["-c", "echo hello"]), None).unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"hello\n");
+    }
+
+    #[test]
+    fn test_run_command_closed_stdin() {
+        let output = run_command(
+            Command::new("sh").args(["-c", "cat; echo done"]),
+            Some(Duration::from_secs(10)),
+        )
+        .unwrap();
+        assert_eq!(output.stdout, b"done\n");
+    }
+
+    #[test]
+    fn test_run_command_timeout() {
+        let start = Instant::now();
+        let result = run_command(
+            Command::new("sleep").arg("10"),
+            Some(Duration::from_millis(100)),
+        );
+        assert!(result.is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_run_command_timeout_open_pipe() {
+        // The background process keeps stdout open after the shell exits
+        let pid_file =
+            std::env::temp_dir().join(format!("cli_doc_grandchild_{}", std::process::id()));
+        let start = Instant::now();
+        let result = run_command(
+            Command::new("sh").args([
+                "-c",
+                &format!("sleep 10 & echo $! > {}; echo hello", pid_file.display()),
+            ]),
+            Some(Duration::from_millis(300)),
+        );
+        assert!(result.is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        // The background process is killed with the shell
+        let pid = std::fs::read_to_string(&pid_file).unwrap();
+        std::fs::remove_file(&pid_file).unwrap();
+        let is_running = || {
+            // A killed process that is not reaped yet is a zombie ("Z")
+            std::fs::read_to_string(format!("/proc/{}/stat", pid.trim())).is_ok_and(|stat| {
+                stat.rsplit(')')
+                    .next()
+                    .unwrap()
+                    .trim_start()
+                    .starts_with(['R', 'S', 'D'])
+            })
+        };
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while is_running() && Instant::now() < deadline {
+            thread::sleep(POLL_INTERVAL);
+        }
+        assert!(!is_running());
+    }
+}
@@ -1,75 +1,560 @@
-use crate::commands::{CommandDesc, CommandId, ProgramDesc};
-use crate::extractor::clap_parser::ClapParser;
-use crate::extractor::sections::parse_sections;
-use anyhow::{anyhow, bail};
-use colored::Colorize;
-use std::collections::HashSet;
+use crate::commands::{
+    CommandDesc, CommandDoc, CommandId, CommandOuterDoc, ExtractionError, ProgramDesc,
+};
+use crate::extractor::ansi::strip_ansi;
+use crate::extractor::capture::{Capture, CaptureMode, CapturedOutput};
+use crate::extractor::environment::ProgramEnvironment;
+use crate::extractor::filter::CommandFilter;
+use crate::extractor::invocation::{HelpInvocation, OutputStream, VersionSource};
+use crate::extractor::parser::{HelpParser, detect_parser};
+use crate::extractor::process::run_command;
+use crate::extractor::sections::{expand_leading_tabs, parse_sections};
+use crate::extractor::value_info::extract_value_info;
+use crate::reporter::{ConsoleReporter, Invocation, Reporter};
+use anyhow::{Context, anyhow};
+use itertools::Itertools;
+use std::borrow::Cow;
+use std::fmt;
 use std::path::Path;
 use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
-fn get_program_output(program: &Path, args: &[String], flag: &str) -> anyhow::Result<String> {
-    print!("Running {}", program.display().to_string().cyan());
-    for arg in args {
-        print!(" {}", arg.yellow());
+#[derive(Default)]
+pub struct RunnerConfig {
+    /// Fixed arguments passed to the program before arguments of subcommands,
+    /// e.g. `-m tool` for `python -m tool`
+    pub program_args: Vec<String>,
+    /// Name of the program in the documentation; the file name of the program by default
+    pub name: Option<String>,
+    /// Parser of help of all commands; it is detected for each command when not set
+    pub parser: Option<Arc<dyn HelpParser>>,
+    /// Maximal number of help commands running at once; 0 means the number of CPUs
+    pub jobs: usize,
+    /// Time limit for a single invocation of the program; no limit when not set
+    pub timeout: Option<Duration>,
+    /// Failures of commands are recorded in the output instead of stopping the extraction
+    pub keep_going: bool,
+    /// Environment variables and working directory of the program
+    pub environment: ProgramEnvironment,
+    /// How help of commands is requested
+    pub help: HelpInvocation,
+    /// How the version of the program is obtained
+    pub version: VersionSource,
+    /// Output stream of the program that is parsed
+    pub output_stream: OutputStream,
+    /// Output of the program is used even when it finishes with a non-zero exit code
+    pub accept_failure: bool,
+    /// Recording or replaying of outputs of the program
+    pub capture: CaptureMode,
+    /// Only subcommands matching one of the patterns (e.g. "remote *") are extracted;
+    /// all are extracted when empty
+    pub include: Vec<String>,
+    /// Subcommands matching one of the patterns (e.g. "debug *") are not extracted
+    pub exclude: Vec<String>,
+    /// Maximal depth of extracted subcommands; 1 means only direct subcommands of the program
+    pub max_depth: Option<usize>,
+    /// Receiver of progress messages; `ConsoleReporter` is used when not set
+    pub reporter: Option<Arc<dyn Reporter>>,
+}
+
+impl RunnerConfig {
+    pub(crate) fn reporter(&self) -> Arc<dyn Reporter> {
+        self.reporter
+            .clone()
+            .unwrap_or_else(|| Arc::new(ConsoleReporter::default()))
     }
-    println!(" {}", flag.magenta());
-    let output = Command::new(program).args(args).arg(flag).output()?;
-    if output.status.success() {
-        Ok(String::from_utf8(output.stdout)
-            .map_err(|_| anyhow!("Invalid output of command with {} flag", flag))?)
-    } else {
-        println!(
-            "{:?}",
-            String::from_utf8(output.stderr).unwrap_or("<INVALID UTF8>".to_string())
-        );
-        bail!("Invalid invocation of command with {} flag", flag)
+}
+
+/// The program finished with a non-zero exit code
+#[derive(Debug)]
+struct ProgramFailure {
+    /// E.g. "--help flag"
+    invocation: String,
+    exit_code: Option<i32>,
+    stderr: String,
+}
+
+impl fmt::Display for ProgramFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid invocation of command with {}", self.invocation)?;
+        if let Some(code) = self.exit_code {
+            write!(f, " (exit code {code})")?;
+        }
+        Ok(())
     }
 }
 
-fn gather_command_helper(
-    program: &Path,
-    args: &mut Vec<String>,
-    id_counter: &mut CommandId,
-) -> anyhow::Result<CommandDesc> {
-    let id = *id_counter;
-    *id_counter += 1;
+impl std::error::Error for ProgramFailure {}
+
+impl From<&anyhow::Error> for ExtractionError {
+    fn from(error: &anyhow::Error) -> Self {
+        let failure = error.downcast_ref::<ProgramFailure>();
+        ExtractionError {
+            message: format!("{error:#}"),
+            exit_code: failure.and_then(|f| f.exit_code),
+            stderr: failure.map(|f| f.stderr.clone()),
+        }
+    }
+}
 
-    let output = get_program_output(program, args.as_slice(), "--help")?;
-    let mut sections = parse_sections(&output);
-    let (command_doc, subcommands) = ClapParser::new().parse(&mut sections)?;
+/// State shared by all invocations of the program during one extraction
+struct Runner<'a> {
+    program: &'a Path,
+    config: &'a RunnerConfig,
+    parser: Option<&'a dyn HelpParser>,
+    jobs: usize,
+    capture: Capture,
+    filter: CommandFilter,
+    reporter: Arc<dyn Reporter>,
+}
 
-    let commands: Vec<_> = subcommands
+/// Calls `f` on all items using at most `jobs` threads; results keep the order of items
+fn map_parallel<T: Sync, R: Send>(items: &[T], jobs: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    if jobs <= 1 || items.len() <= 1 {
+        return items.iter().map(f).collect();
+    }
+    let next = AtomicUsize::new(0);
+    let results: Vec<Mutex<Option<R>>> = items.iter().map(|_| Mutex::new(None)).collect();
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            scope.spawn(|| {
+                loop {
+                    let idx = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(idx) else {
+                        break;
+                    };
+                    *results[idx].lock().unwrap() = Some(f(item));
+                }
+            });
+        }
+    });
+    results
         .into_iter()
-        .filter(|s| s.name != "help")
-        .map(|s| {
-            args.push(s.name.to_string());
-            let r = gather_command_helper(program, args, id_counter);
-            args.pop();
-            r
+        .map(|r| r.into_inner().unwrap().unwrap())
+        .collect()
+}
+
+impl Runner<'_> {
+    fn display_name(&self) -> String {
+        self.config.name.clone().unwrap_or_else(|| {
+            self.program
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string()
         })
-        .collect::<anyhow::Result<Vec<_>>>()?;
-
-    let name = args
-        .last()
-        .map(|x| x.to_string())
-        .unwrap_or_else(|| program.file_name().unwrap().to_string_lossy().to_string());
-
-    Ok(CommandDesc {
-        id,
-        name,
-        doc: command_doc,
-        commands,
-    })
+    }
+
+    /// Gets the output of the program with `args` following the fixed program arguments;
+    /// `purpose` describes the arguments in errors (e.g. "--help flag")
+    fn get_program_output(&self, args: &[String], purpose: &str) -> anyhow::Result<String> {
+        let replay = matches!(self.config.capture, CaptureMode::Replay(_));
+        let invocation = Invocation {
+            program: self.program,
+            program_args: &self.config.program_args,
+            args,
+            replayed: replay,
+        };
+        self.reporter.command_started(&invocation);
+        let start = Instant::now();
+        let result = if replay {
+            self.capture
+                .get(args)
+                .ok_or_else(|| anyhow!("Output of `{}` not found in the capture", args.join(" ")))
+        } else {
+            self.run_program(args).inspect(|result| {
+                if let CaptureMode::Record(_) = self.config.capture {
+                    self.capture.insert(args, result.clone());
+                }
+            })
+        };
+        // The context of the error repeats the invocation
+        let result = result.inspect_err(|error| {
+            self.reporter
+                .command_error(&invocation, &error.root_cause().to_string())
+        })?;
+        match result {
+            CapturedOutput::Output(output) => {
+                self.reporter.command_finished(&invocation, start.elapsed());
+                Ok(strip_ansi(&output))
+            }
+            CapturedOutput::Failure { exit_code, stderr } => {
+                let stderr = strip_ansi(&stderr);
+                self.reporter
+                    .command_failed(&invocation, exit_code, &stderr);
+                Err(ProgramFailure {
+                    invocation: purpose.to_string(),
+                    exit_code,
+                    stderr,
+                }
+                .into())
+            }
+        }
+    }
+
+    fn command_line(&self, args: &[String]) -> String {
+        let mut command_line = self.program.display().to_string();
+        for arg in self.config.program_args.iter().chain(args) {
+            command_line.push(' ');
+            command_line.push_str(arg);
+        }
+        command_line
+    }
+
+    /// Invalid UTF-8 sequences are replaced, so a single odd byte does not stop the extraction
+    fn decode_output(&self, args: &[String], bytes: &[u8]) -> String {
+        let text = String::from_utf8_lossy(bytes);
+        if let Cow::Owned(_) = text {
+            self.reporter.warning(&format!(
+                "Output of `{}` is not valid UTF-8; invalid bytes were replaced",
+                self.command_line(args)
+            ));
+        }
+        text.into_owned()
+    }
+
+    fn run_program(&self, args: &[String]) -> anyhow::Result<CapturedOutput> {
+        let mut command = Command::new(self.program);
+        command.args(&self.config.program_args).args(args);
+        self.config.environment.apply(&mut command);
+        let output = run_command(&mut command, self.config.timeout)
+            .with_context(|| format!("Running `{}` failed", self.command_line(args)))?;
+        let selected = self.config.output_stream.select(&output);
+        if output.status.success()
+            || (self.config.accept_failure && !selected.trim_ascii().is_empty())
+        {
+            Ok(CapturedOutput::Output(self.decode_output(args, selected)))
+        } else {
+            Ok(CapturedOutput::Failure {
+                exit_code: output.status.code(),
+                stderr: self.decode_output(args, &output.stderr),
+            })
+        }
+    }
+
+    fn extract_command(
+        &self,
+        args: &[String],
+    ) -> anyhow::Result<(CommandDoc, Vec<CommandOuterDoc>)> {
+        let help = &self.config.help;
+        let output =
+            expand_leading_tabs(&self.get_program_output(&help.arguments(args), &help.describe())?);
+        let mut sections = parse_sections(&output);
+        let (mut command_doc, subcommands) = match self.parser {
+            Some(parser) => parser,
+            None => detect_parser(&sections)?,
+        }
+        .parse(&mut sections)?;
+        extract_value_info(&mut command_doc);
+        Ok((command_doc, subcommands))
+    }
+
+    /// Extracts all commands of one level of the command tree at once, so
+    /// siblings (and cousins) are processed in parallel
+    fn gather_commands(
+        &self,
+        paths: Vec<(Vec<String>, Option<CommandOuterDoc>)>,
+    ) -> anyhow::Result<Vec<CommandDesc>> {
+        let results = map_parallel(&paths, self.jobs, |(args, _)| self.extract_command(args));
+
+        let mut level = Vec::with_capacity(paths.len());
+        let mut next_paths = Vec::new();
+        for ((args, outer), result) in paths.into_iter().zip(results) {
+            let (mut doc, subcommands, error) = match result {
+                Ok((doc, subcommands)) => (doc, subcommands, None),
+                Err(e) if self.config.keep_going => {
+                    let error = ExtractionError::from(&e);
+                    self.reporter.warning(&format!(
+                        "{}: {}",
+                        [self.display_name()].iter().chain(&args).join(" "),
+                        error.message.lines().next().unwrap_or_default()
+                    ));
+                    (CommandDoc::default(), Vec::new(), Some(error))
+                }
+                Err(e) => return Err(e),
+            };
+            let mut n_children = 0;
+            for s in subcommands {
+                if s.name == self.config.help.command_name() {
+                    continue;
+                }
+                let mut child_args = args.clone();
+                child_args.push(s.name.clone());
+                if self.filter.accepts(&child_args) {
+                    n_children += 1;
+                    next_paths.push((child_args, Some(s)));
+                } else {
+                    self.reporter
+                        .command_skipped(&self.display_name(), &child_args);
+                }
+            }
+            let name = args
+                .last()
+                .map(|x| x.to_string())
+                .unwrap_or_else(|| self.display_name());
+            let mut aliases = Vec::new();
+            if let Some(outer) = outer {
+                aliases = outer.aliases;
+                // Some formats (e.g. man pages) have no brief, the one from the parent is used
+                if doc.brief.is_empty() {
+                    doc.brief = outer.brief;
+                }
+            }
+            level.push((
+                CommandDesc {
+                    id: 0,
+                    name,
+                    aliases,
+                    doc,
+                    error,
+                    commands: Vec::new(),
+                },
+                n_children,
+            ));
+        }
+
+        let mut children = if next_paths.is_empty() {
+            Vec::new()
+        } else {
+            self.gather_commands(next_paths)?
+        }
+        .into_iter();
+        Ok(level
+            .into_iter()
+            .map(|(mut command, n_children)| {
+                command.commands = children.by_ref().take(n_children).collect();
+                command
+            })
+            .collect())
+    }
 }
 
-pub fn collect_program_info(program: &Path) -> anyhow::Result<ProgramDesc> {
-    let mut args: Vec<String> = Vec::new();
-    let mut id_counter = 0;
-    let mut already_defined = HashSet::new();
-    already_defined.insert("-h, --help");
-    let command = gather_command_helper(program, &mut args, &mut id_counter)?;
-    let version = get_program_output(program, &args, "--version")?
-        .trim()
-        .to_string();
+/// Ids are assigned in depth-first order, independently on the order of extraction
+fn assign_ids(command: &mut CommandDesc, id_counter: &mut CommandId) {
+    command.id = *id_counter;
+    *id_counter += 1;
+    for child in &mut command.commands {
+        assign_ids(child, id_counter);
+    }
+}
+
+pub fn collect_program_info(program: &Path, config: &RunnerConfig) -> anyhow::Result<ProgramDesc> {
+    // A relative path to the program would be resolved against the other working directory
+    let program = if config.environment.working_dir.is_some()
+        && !matches!(config.capture, CaptureMode::Replay(_))
+        && program.parent().is_some_and(|p| !p.as_os_str().is_empty())
+    {
+        Cow::Owned(
+            program
+                .canonicalize()
+                .with_context(|| format!("Program `{}` not found", program.display()))?,
+        )
+    } else {
+        Cow::Borrowed(program)
+    };
+    let runner = Runner {
+        program: &program,
+        config,
+        parser: config.parser.as_deref(),
+        jobs: if config.jobs == 0 {
+            thread::available_parallelism().map_or(1, |n| n.get())
+        } else {
+            config.jobs
+        },
+        capture: match &config.capture {
+            CaptureMode::Replay(path) => Capture::load(path)?,
+            _ => Capture::default(),
+        },
+        filter: CommandFilter::new(&config.include, &config.exclude, config.max_depth),
+        reporter: config.reporter(),
+    };
+    let mut command = runner
+        .gather_commands(vec![(Vec::new(), None)])?
+        .pop()
+        .unwrap();
+    assign_ids(&mut command, &mut 0);
+    let version = match &config.version {
+        VersionSource::Fixed(version) => Ok(version.clone()),
+        VersionSource::Flag(flag) => runner
+            .get_program_output(std::slice::from_ref(flag), &format!("{flag} flag"))
+            .map(|version| version.trim().to_string()),
+    };
+    let version = match version {
+        Ok(version) => version,
+        Err(e) if config.keep_going => {
+            runner
+                .reporter
+                .warning(&format!("Version not found: {e:#}"));
+            String::new()
+        }
+        Err(e) => return Err(e),
+    };
+    if config.keep_going {
+        report_failures(&command, runner.reporter.as_ref());
+    }
+    if let CaptureMode::Record(path) = &config.capture {
+        runner.capture.save(path)?;
+        runner.reporter.capture_saved(path);
+    }
     Ok(ProgramDesc { command, version })
 }
+
+fn collect_failures<'a>(
+    command: &'a CommandDesc,
+    path: &mut Vec<&'a str>,
+    out: &mut Vec<(String, &'a ExtractionError)>,
+) {
+    path.push(&command.name);
+    if let Some(error) = &command.error {
+        out.push((path.join(" "), error));
+    }
+    for child in &command.commands {
+        collect_failures(child, path, out);
+    }
+    path.pop();
+}
+
+fn report_failures(command: &CommandDesc, reporter: &dyn Reporter) {
+    let mut failures = Vec::new();
+    collect_failures(command, &mut Vec::new(), &mut failures);
+    if failures.is_empty() {
+        return;
+    }
+    let mut message = format!(
+        "Documentation of {} command(s) could not be extracted:",
+        failures.len()
+    );
+    for (path, error) in failures {
+        message.push_str(&format!(
+            "\n  {}: {}",
+            path,
+            error.message.lines().next().unwrap_or_default()
+        ));
+    }
+    reporter.warning(&message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_parallel_keeps_order() {
+        let items: Vec<u64> = (0..50).collect();
+        let results = map_parallel(&items, 8, |x| {
+            thread::sleep(std::time::Duration::from_millis(50 - x));
+            x * 2
+        });
+        assert_eq!(results, items.iter().map(|x| x * 2).collect::<Vec<_>>());
+    }
+
+    /// Collects all events as lines
+    #[derive(Default)]
+    struct RecordingReporter {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl Reporter for RecordingReporter {
+        fn command_started(&self, invocation: &Invocation) {
+            let event = format!("started {invocation}");
+            self.events.lock().unwrap().push(event);
+        }
+
+        fn command_failed(&self, invocation: &Invocation, exit_code: Option<i32>, _stderr: &str) {
+            let event = format!("failed {invocation} {exit_code:?}");
+            self.events.lock().unwrap().push(event);
+        }
+
+        fn command_error(&self, invocation: &Invocation, error: &str) {
+            let event = format!("error {invocation}: {error}");
+            self.events.lock().unwrap().push(event);
+        }
+
+        fn warning(&self, message: &str) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("warning {message}"));
+        }
+    }
+
+    #[test]
+    fn test_replay_capture() {
+        let capture = r#"{
+          "format_version": 1,
+          "invocations": [
+            {"args": ["--help"], "output": "Fake program\n\nUsage: prog <COMMAND>\n\nCommands:\n  run, r  Run something\n  list    List things\n  gone    Not in the capture\n  help    Print this message or the help of the given subcommand(s)\n\nOptions:\n  -h, --help  Print help\n"},
+            {"args": ["--version"], "output": "prog 1.2.3\n"},
+            {"args": ["list", "--help"], "failure": {"exit_code": 3, "stderr": "error: list is broken\n"}},
+            {"args": ["run", "--help"], "output": "Run something\n\nUsage: prog run <FILE>\n\nArguments:\n  <FILE>  File to run\n"}
+          ]
+        }"#;
+        let path =
+            std::env::temp_dir().join(format!("cli_doc_capture_{}.json", std::process::id()));
+        std::fs::write(&path, capture).unwrap();
+        let reporter = Arc::new(RecordingReporter::default());
+        let config = RunnerConfig {
+            jobs: 1,
+            keep_going: true,
+            capture: CaptureMode::Replay(path.clone()),
+            reporter: Some(reporter.clone()),
+            ..Default::default()
+        };
+        let program = collect_program_info(Path::new("/nonexistent/prog"), &config);
+        std::fs::remove_file(&path).unwrap();
+        let program = program.unwrap();
+        assert_eq!(program.version, "prog 1.2.3");
+        let commands = &program.command.commands;
+        assert_eq!(commands.len(), 3);
+        assert_eq!(commands[0].name, "run");
+        assert_eq!(commands[0].aliases, ["r"]);
+        assert_eq!(commands[0].doc.arguments[0].name, "<FILE>");
+        assert_eq!(commands[1].name, "list");
+        assert_eq!(commands[1].error.as_ref().unwrap().exit_code, Some(3));
+        assert_eq!(commands[2].name, "gone");
+        assert_eq!(commands[2].error.as_ref().unwrap().exit_code, None);
+        assert_eq!(
+            *reporter.events.lock().unwrap(),
+            [
+                "started /nonexistent/prog --help",
+                "started /nonexistent/prog run --help",
+                "started /nonexistent/prog list --help",
+                "failed /nonexistent/prog list --help Some(3)",
+                "started /nonexistent/prog gone --help",
+                "error /nonexistent/prog gone --help: \
+                 Output of `gone --help` not found in the capture",
+                "warning prog list: Invalid invocation of command with --help flag (exit code 3)",
+                "warning prog gone: Output of `gone --help` not found in the capture",
+                "started /nonexistent/prog --version",
+                "warning Documentation of 2 command(s) could not be extracted:\n  \
+                 prog list: Invalid invocation of command with --help flag (exit code 3)\n  \
+                 prog gone: Output of `gone --help` not found in the capture",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_launcher() {
+        // The launcher passes arguments after "--" to the documented program
+        let script = r#"[ "$1" = -- ] || exit 2
+shift
+case "$*" in
+  --help) printf 'Tool\n\nUsage: tool [OPTIONS]\n\nOptions:\n  -h, --help  Print help\n' ;;
+  --version) echo "tool 1.0" ;;
+  *) exit 2 ;;
+esac"#;
+        let config = RunnerConfig {
+            program_args: ["-c", script, "launcher", "--"].map(String::from).to_vec(),
+            name: Some("tool".to_string()),
+            jobs: 1,
+            ..Default::default()
+        };
+        let program = collect_program_info(Path::new("sh"), &config).unwrap();
+        assert_eq!(program.version, "tool 1.0");
+        assert_eq!(program.command.name, "tool");
+        assert_eq!(program.command.doc.brief.to_text(), "Tool");
+    }
+}
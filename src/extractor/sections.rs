@@ -1,8 +1,8 @@
 use crate::text::RichText;
 
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq, Eq, Clone))]
-pub(crate) struct Section<'a> {
+/// A paragraph of a help output with nested (more indented) sections
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section<'a> {
     paragraph: Vec<&'a str>,
     subsections: Vec<Section<'a>>,
     indent: usize,
@@ -25,12 +25,33 @@ impl<'a> Section<'a> {
         &self.subsections
     }
 
+    /// Indentation relative to the parent section
+    pub fn indent(&self) -> usize {
+        self.indent
+    }
+
     pub fn flatten_child_lines(&self) -> impl Iterator<Item = &'a str> {
         self.subsections
             .iter()
             .flat_map(|s| s.lines().iter().copied())
     }
 
+    /// Lines of all (nested) subsections in the original order together with
+    /// their indentation relative to this section
+    pub fn child_lines_with_indent(&self) -> Vec<(usize, &'a str)> {
+        let mut out = Vec::new();
+        self.child_lines_with_indent_helper(0, &mut out);
+        out
+    }
+
+    fn child_lines_with_indent_helper(&self, base: usize, out: &mut Vec<(usize, &'a str)>) {
+        for section in &self.subsections {
+            let indent = base + section.indent;
+            out.extend(section.paragraph.iter().map(|line| (indent, *line)));
+            section.child_lines_with_indent_helper(indent, out);
+        }
+    }
+
     pub fn extract_sections_upto_ident<'b, 'c>(
         &'b mut self,
         indent: usize,
@@ -163,6 +184,25 @@ pub(crate) fn parse_sections(input: &str) -> Vec<Section<'_>> {
     read_sections(&mut reader, 0, 0)
 }
 
+/// Replaces leading tabs by spaces, so they are counted into the indentation
+pub(crate) fn expand_leading_tabs(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for line in input.split_inclusive('\n') {
+        let rest = line.trim_start_matches([' ', '\t']);
+        let mut width = 0;
+        for c in line[..line.len() - rest.len()].chars() {
+            width = if c == '\t' {
+                (width / 8 + 1) * 8
+            } else {
+                width + 1
+            };
+        }
+        out.extend(std::iter::repeat_n(' ', width));
+        out.push_str(rest);
+    }
+    out
+}
+
 fn compute_indentation(text: &str) -> usize {
     text.chars().take_while(|&c| c == ' ').count()
 }
@@ -982,6 +1022,15 @@ Run `cargo help add` for more detailed information.
         "#);
     }
 
+    #[test]
+    pub fn test_expand_leading_tabs() {
+        assert_eq!(
+            expand_leading_tabs("a\tb\n\tc\n  \td\n"),
+            "a\tb\n        c\n        d\n"
+        );
+        assert_eq!(expand_leading_tabs("x"), "x");
+    }
+
     #[test]
     pub fn test_parse_output() {
         assert!(parse_sections("").is_empty());
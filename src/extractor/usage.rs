@@ -0,0 +1,134 @@
+use crate::commands::{CommandOuterDoc, Usage, UsagePart};
+use crate::extractor::sections::Section;
+use crate::text::RichText;
+
+/// Splits usage into words while keeping bracketed groups like "[-a | --all]" together
+///
+/// Plain words are commands until the first option or argument appears; after that,
+/// they are treated as arguments (e.g. "prog sub [-h] target").
+pub(crate) fn parse_grouped_usage(input: &str) -> Usage {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0i32;
+    for word in input.split_whitespace() {
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+        for c in word.chars() {
+            match c {
+                '[' | '(' | '{' => depth += 1,
+                ']' | ')' | '}' => depth -= 1,
+                _ => {}
+            }
+        }
+        if depth <= 0 {
+            depth = 0;
+            let s = std::mem::take(&mut current);
+            let in_arguments = parts.iter().any(|p| !matches!(p, UsagePart::Command(_)));
+            parts.push(if s.starts_with('[') || s.starts_with('-') {
+                UsagePart::Option(s)
+            } else if s.starts_with('<') || s.starts_with('{') || in_arguments {
+                UsagePart::Argument(s)
+            } else {
+                UsagePart::Command(s)
+            });
+        }
+    }
+    if !current.is_empty() {
+        parts.push(UsagePart::Option(current));
+    }
+    Usage { parts }
+}
+
+/// Splits names of an option into the short name and the rest, e.g. "-m <msg>" and
+/// "--message=<msg>"; the short name is listed first when the option has one
+pub(crate) fn split_short_long(names: &[&str]) -> (Option<String>, String) {
+    if names.len() > 1 && !names[0].starts_with("--") {
+        (Some(names[0].to_string()), names[1..].join(", "))
+    } else {
+        (None, names.join(", "))
+    }
+}
+
+/// Title of a section grouping other sections, e.g. "Available Commands:" followed
+/// by indented entries
+pub(crate) fn group_title<'a>(section: &Section<'a>) -> Option<&'a str> {
+    if section.lines().len() != 1 || section.subsections().is_empty() {
+        return None;
+    }
+    section.first_line().strip_suffix(':')
+}
+
+/// Subcommands listed in a section with the name and the brief on each line,
+/// e.g. "run   Run something"
+pub(crate) fn parse_command_list(section: &Section) -> Vec<CommandOuterDoc> {
+    section
+        .flatten_child_lines()
+        .map(|line| {
+            let (name, brief) = line.split_once(' ').unwrap_or((line, ""));
+            CommandOuterDoc {
+                name: name.to_string(),
+                aliases: Vec::new(),
+                brief: RichText::from_single_line(brief.trim()),
+            }
+        })
+        .collect()
+}
+
+/// Splits text before the first section into a brief (the first paragraph) and
+/// a description, for formats without a dedicated brief
+pub(crate) fn split_brief(text: RichText) -> (RichText, Option<RichText>) {
+    let mut parts = text.parts.into_iter();
+    let brief = RichText {
+        parts: parts.next().into_iter().collect(),
+    };
+    let description = RichText {
+        parts: parts.collect(),
+    };
+    (
+        brief,
+        if description.is_empty() {
+            None
+        } else {
+            Some(description)
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_grouped_usage() {
+        let usage =
+            parse_grouped_usage("tool build [-h] [-f {json, yaml}] -x {build,run} ... target");
+        assert_eq!(
+            usage.parts,
+            vec![
+                UsagePart::Command("tool".to_string()),
+                UsagePart::Command("build".to_string()),
+                UsagePart::Option("[-h]".to_string()),
+                UsagePart::Option("[-f {json, yaml}]".to_string()),
+                UsagePart::Option("-x".to_string()),
+                UsagePart::Argument("{build,run}".to_string()),
+                UsagePart::Argument("...".to_string()),
+                UsagePart::Argument("target".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_short_long() {
+        assert_eq!(
+            split_short_long(&["-m <msg>", "--message=<msg>"]),
+            (Some("-m <msg>".to_string()), "--message=<msg>".to_string())
+        );
+        assert_eq!(
+            split_short_long(&["--color", "--colour"]),
+            (None, "--color, --colour".to_string())
+        );
+        assert_eq!(split_short_long(&["-v"]), (None, "-v".to_string()));
+    }
+}
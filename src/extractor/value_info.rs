@@ -0,0 +1,353 @@
+use crate::commands::{CommandDoc, PossibleValue, ValueInfo};
+use crate::text::{RichText, RichTextPart};
+
+/// Moves annotations like "[default: auto]" or "[env: FOO=]" from the help texts
+/// of options and arguments into their `ValueInfo`
+pub(crate) fn extract_value_info(doc: &mut CommandDoc) {
+    for arg in &mut doc.arguments {
+        arg.info.value_name = Some(argument_value_name(&arg.name));
+        take_annotations(&mut arg.brief, &mut arg.info);
+        if let Some(description) = &mut arg.description {
+            take_annotations(description, &mut arg.info);
+        }
+        if arg.description.as_ref().is_some_and(|d| d.is_empty()) {
+            arg.description = None;
+        }
+    }
+    for option in doc
+        .option_categories
+        .iter_mut()
+        .flat_map(|c| c.options.iter_mut())
+    {
+        option.info.value_name = option_value_name(&option.long)
+            .or_else(|| option.short.as_deref().and_then(option_value_name));
+        take_annotations(&mut option.brief, &mut option.info);
+        if let Some(description) = &mut option.description {
+            take_annotations(description, &mut option.info);
+        }
+        if option.description.as_ref().is_some_and(|d| d.is_empty()) {
+            option.description = None;
+        }
+        if option.info.possible_values.is_empty()
+            && let Some(values) = option
+                .info
+                .value_name
+                .as_deref()
+                .and_then(enumerated_values)
+        {
+            option.info.possible_values = values.into_iter().map(PossibleValue::new).collect();
+        }
+    }
+}
+
+/// Returns false if the key is not recognized
+fn set_value(info: &mut ValueInfo, key: &str, value: &str) -> bool {
+    match key {
+        "default" => info.default = Some(value.to_string()),
+        "possible values" => {
+            info.possible_values = split_list(value)
+                .into_iter()
+                .map(PossibleValue::new)
+                .collect()
+        }
+        // The current value may follow, e.g. "[env: FOO=bar]"
+        "env" | "env var" => info.env = Some(value.split('=').next().unwrap_or(value).to_string()),
+        "alias" | "aliases" => info.aliases.extend(split_list(value)),
+        _ => return false,
+    }
+    true
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value.split(", ").map(|s| s.trim().to_string()).collect()
+}
+
+fn take_annotations(text: &mut RichText, info: &mut ValueInfo) {
+    let mut parts = Vec::with_capacity(text.parts.len());
+    let mut in_possible_values = false;
+    for part in std::mem::take(&mut text.parts) {
+        match part {
+            RichTextPart::Config { key, value } if set_value(info, &key, &value) => {}
+            RichTextPart::Text(s) => {
+                let s = take_trailing_annotations(&s, info);
+                // Long help of clap lists possible values together with their help
+                in_possible_values = s == "Possible values:";
+                if !s.is_empty() {
+                    parts.push(RichTextPart::Text(s.to_string()));
+                }
+            }
+            RichTextPart::Ul(items) if in_possible_values => {
+                info.possible_values = items.iter().map(|item| possible_value(item)).collect();
+                // The values are shown from the info, so the list is not repeated
+                parts.pop();
+                in_possible_values = false;
+            }
+            part => parts.push(part),
+        }
+    }
+    text.parts = parts;
+}
+
+/// Item of the list of possible values in the long help of clap, e.g. "json: JSON lines"
+fn possible_value(item: &str) -> PossibleValue {
+    match item.split_once(':') {
+        Some((name, help)) => PossibleValue {
+            name: name.trim().to_string(),
+            help: Some(help.trim().to_string()).filter(|help| !help.is_empty()),
+        },
+        None => PossibleValue::new(item.trim()),
+    }
+}
+
+/// Strips recognized annotations from the end of a text, e.g. "Coloring [possible values: auto, never]"
+fn take_trailing_annotations<'a>(mut s: &'a str, info: &mut ValueInfo) -> &'a str {
+    while let Some(rest) = s.trim_end().strip_suffix(']') {
+        let Some(start) = rest.rfind('[') else {
+            break;
+        };
+        let Some((key, value)) = rest[start + 1..].split_once(':') else {
+            break;
+        };
+        if !set_value(info, key.trim(), value.trim()) {
+            break;
+        }
+        s = rest[..start].trim_end();
+    }
+    s
+}
+
+/// Value placeholder of an option, e.g. "NAME" for "--name <NAME>" or "INTEGER" for "--count INTEGER"
+fn option_value_name(option: &str) -> Option<String> {
+    let option = option.trim();
+    let value = if let Some((_, value)) = option.split_once(' ') {
+        value.trim()
+    } else if let Some((_, value)) = option.split_once("[=") {
+        // Optional value, e.g. "--color[=<WHEN>]"
+        value
+            .strip_suffix(']')
+            .filter(|value| value.starts_with('<'))?
+    } else {
+        // Only placeholders are taken after '=', "--flatten=false" shows the default value
+        option
+            .split_once('=')
+            .map(|(_, value)| value)
+            .filter(|value| value.starts_with('<'))?
+    };
+    // "--debug / --no-debug" or more names separated by commas
+    if value.starts_with(['/', '-', ',']) || value.contains(" --") {
+        return None;
+    }
+    Some(strip_angle_brackets(strip_optional(value)).to_string())
+}
+
+/// Value name of an argument, e.g. "FILE" for "[FILE]..." or "<FILE>"
+fn argument_value_name(name: &str) -> String {
+    let name = name.trim().trim_end_matches("...");
+    let name = name
+        .strip_prefix('[')
+        .and_then(|n| n.strip_suffix(']'))
+        .unwrap_or(name);
+    strip_angle_brackets(name).to_string()
+}
+
+/// Placeholder of an optional value, e.g. "<SPEC>" for "[<SPEC>]"; choices like "[fast|slow]"
+/// are kept
+fn strip_optional(s: &str) -> &str {
+    s.strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .filter(|s| s.starts_with('<'))
+        .unwrap_or(s)
+}
+
+fn strip_angle_brackets(s: &str) -> &str {
+    s.strip_prefix('<')
+        .and_then(|s| s.strip_suffix('>'))
+        .unwrap_or(s)
+}
+
+/// Choices embedded in the value name, e.g. "{json,yaml}" (argparse) or "[fast|slow]" (click)
+fn enumerated_values(value_name: &str) -> Option<Vec<String>> {
+    let (inner, separator) = if let Some(inner) = value_name
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+    {
+        (inner, ',')
+    } else {
+        (
+            value_name
+                .strip_prefix('[')
+                .and_then(|s| s.strip_suffix(']'))?,
+            '|',
+        )
+    };
+    if !inner.contains(separator) {
+        return None;
+    }
+    Some(
+        inner
+            .split(separator)
+            .map(|s| s.trim().to_string())
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::fixtures::parse_clap;
+    use crate::commands::{ArgumentDesc, CategoryDesc, OptionDesc};
+
+    fn option(long: &str, brief: &str, description: &[&str]) -> OptionDesc {
+        let mut desc = RichText::new();
+        desc.add_lines(description);
+        OptionDesc {
+            short: None,
+            long: long.to_string(),
+            brief: RichText::from_single_line(brief),
+            description: if desc.is_empty() { None } else { Some(desc) },
+            info: ValueInfo::default(),
+        }
+    }
+
+    #[test]
+    fn test_extract_value_info() {
+        let mut doc = CommandDoc {
+            brief: RichText::new(),
+            description: None,
+            usage: Vec::new(),
+            arguments: vec![ArgumentDesc {
+                name: "[PATHS]...".to_string(),
+                brief: RichText::from_single_line("Paths to check [default: .]"),
+                description: None,
+                info: ValueInfo::default(),
+            }],
+            option_categories: vec![CategoryDesc {
+                title: "Options".to_string(),
+                options: vec![
+                    option(
+                        "--color <WHEN>",
+                        "Coloring [default: auto] [possible values: auto, always, never]",
+                        &[],
+                    ),
+                    option(
+                        "--server-dir <DIR>",
+                        "Path to the server directory",
+                        &["[env: HQ_SERVER_DIR=]", "[aliases: --dir, -d]"],
+                    ),
+                    option(
+                        "--mode <MODE>",
+                        "Output mode",
+                        &["Possible values:", "- cli: Human readable", "- json: JSON"],
+                    ),
+                    option("--count INTEGER", "Count  [default: 1]", &[]),
+                    option("--format {json,yaml}", "Format", &[]),
+                    option("--debug / --no-debug", "Debug", &[]),
+                    option("--flatten=false", "Flatten [required]", &[]),
+                    option("--package [<SPEC>]", "Package to build", &[]),
+                    option("--color[=<WHEN>]", "Coloring", &[]),
+                    option("--mode [fast|slow]", "Mode", &[]),
+                ],
+            }],
+            groups: Vec::new(),
+        };
+        extract_value_info(&mut doc);
+        assert_eq!(
+            doc.arguments[0].info,
+            ValueInfo {
+                value_name: Some("PATHS".to_string()),
+                default: Some(".".to_string()),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            doc.arguments[0].brief,
+            RichText::from_single_line("Paths to check")
+        );
+
+        let options = &doc.option_categories[0].options;
+        assert_eq!(
+            options[0].info,
+            ValueInfo {
+                value_name: Some("WHEN".to_string()),
+                default: Some("auto".to_string()),
+                possible_values: vec![
+                    PossibleValue::new("auto"),
+                    PossibleValue::new("always"),
+                    PossibleValue::new("never")
+                ],
+                ..Default::default()
+            }
+        );
+        assert_eq!(options[0].brief, RichText::from_single_line("Coloring"));
+        assert_eq!(options[1].info.env.as_deref(), Some("HQ_SERVER_DIR"));
+        assert_eq!(options[1].info.aliases, vec!["--dir", "-d"]);
+        assert_eq!(options[1].description, None);
+        assert_eq!(
+            options[2].info.possible_values,
+            vec![
+                PossibleValue {
+                    name: "cli".to_string(),
+                    help: Some("Human readable".to_string()),
+                },
+                PossibleValue {
+                    name: "json".to_string(),
+                    help: Some("JSON".to_string()),
+                },
+            ]
+        );
+        assert_eq!(options[2].description, None);
+        assert_eq!(options[3].info.value_name.as_deref(), Some("INTEGER"));
+        assert_eq!(options[3].info.default.as_deref(), Some("1"));
+        assert_eq!(
+            options[4].info.possible_values,
+            vec![PossibleValue::new("json"), PossibleValue::new("yaml")]
+        );
+        assert_eq!(options[5].info.value_name, None);
+        assert_eq!(options[6].info.value_name, None);
+        assert_eq!(
+            options[6].brief,
+            RichText::from_single_line("Flatten [required]")
+        );
+        assert_eq!(options[7].info.value_name.as_deref(), Some("SPEC"));
+        assert_eq!(options[8].info.value_name.as_deref(), Some("WHEN"));
+        assert_eq!(
+            options[9].info.possible_values,
+            vec![PossibleValue::new("fast"), PossibleValue::new("slow")]
+        );
+    }
+
+    #[test]
+    fn test_long_help_possible_values() {
+        let doc = parse_clap(
+            "Usage: prog [OPTIONS]
+
+Options:
+      --mode <MODE>
+          Output mode
+
+          Possible values:
+          - cli:  Human readable table
+          - json: Machine readable JSON lines
+          - raw
+",
+        );
+        let option = &doc.option_categories[0].options[0];
+        assert_eq!(option.description, None);
+        assert_eq!(
+            option.info.described_values(),
+            [
+                PossibleValue {
+                    name: "cli".to_string(),
+                    help: Some("Human readable table".to_string()),
+                },
+                PossibleValue {
+                    name: "json".to_string(),
+                    help: Some("Machine readable JSON lines".to_string()),
+                },
+                PossibleValue::new("raw"),
+            ]
+        );
+        // The values are listed with their help instead of the detail
+        assert!(option.info.details().is_empty());
+    }
+}
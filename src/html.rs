@@ -1,8 +1,10 @@
-use crate::commands::{CommandDesc, CommandId, ProgramDesc};
+use crate::commands::{CommandDesc, CommandId, ProgramDesc, ValueInfo};
+use crate::json::parse_json;
+use anyhow::anyhow;
 use askama::Template;
 use askama::filters::{Escaper, Html};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 #[derive(Template)]
 #[template(path = "page.html")]
@@ -11,13 +13,20 @@ struct PageTemplate<'a> {
     version: &'a str,
     command: CommandTemplate<'a>,
     command_json: String,
+    /// The model as in the JSON output, so it can be read back from the page
+    model_json: String,
     generator: String,
+    /// CSS appended to the default style
+    theme: Option<&'a str>,
 }
 
 #[derive(Template)]
 #[template(path = "command.html", escape = "none")]
 struct CommandTemplate<'a> {
     name: &'a str,
+    /// Escaped and comma separated
+    aliases: String,
+    failed: bool,
     id: CommandId,
     depth: u32,
     subcommands: Vec<CommandTemplate<'a>>,
@@ -30,6 +39,7 @@ struct OptionJson<'a> {
     long: String,
     brief: String,
     description: Option<String>,
+    info: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -37,6 +47,14 @@ struct ArgumentJson {
     name: String,
     brief: String,
     description: Option<String>,
+    info: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ErrorJson {
+    message: String,
+    exit_code: Option<i32>,
+    stderr: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -48,6 +66,8 @@ struct CategoryJson<'a> {
 #[derive(Serialize)]
 struct CommandJson<'a> {
     name: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    aliases: Vec<String>,
     parent: Option<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     children: Vec<String>,
@@ -56,6 +76,10 @@ struct CommandJson<'a> {
     usages: Vec<String>,
     arguments: Vec<ArgumentJson>,
     categories: Vec<CategoryJson<'a>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    groups: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ErrorJson>,
 }
 
 impl<'a> CommandJson<'a> {
@@ -63,6 +87,7 @@ impl<'a> CommandJson<'a> {
         let mut option_id = 0;
         CommandJson {
             name: desc.name.to_string(),
+            aliases: desc.aliases.iter().map(|a| escape_html(a)).collect(),
             parent: parent.map(|x| x.to_string()),
             children: desc.commands.iter().map(|c| format!("c{}", c.id)).collect(),
             brief: desc.doc.brief.to_html(),
@@ -78,6 +103,7 @@ impl<'a> CommandJson<'a> {
                         name: escape_html(&a.name),
                         brief: a.brief.to_html(),
                         description: a.description.as_ref().map(|t| t.to_html()),
+                        info: value_info_to_html(&a.info),
                     })
                     .collect()
             },
@@ -99,10 +125,29 @@ impl<'a> CommandJson<'a> {
                             long: escape_html(&o.long),
                             brief: o.brief.to_html(),
                             description: o.description.as_ref().map(|t| t.to_html()),
+                            info: value_info_to_html(&o.info),
                         })
                         .collect(),
                 })
                 .collect(),
+            groups: desc
+                .doc
+                .groups
+                .iter()
+                .map(|g| {
+                    let members: Vec<_> = g
+                        .members
+                        .iter()
+                        .map(|m| format!("<code>{}</code>", escape_html(m)))
+                        .collect();
+                    format!("{} {}", g.constraint(), members.join(", "))
+                })
+                .collect(),
+            error: desc.error.as_ref().map(|e| ErrorJson {
+                message: escape_html(&e.message),
+                exit_code: e.exit_code,
+                stderr: e.stderr.as_deref().map(escape_html),
+            }),
         }
     }
 }
@@ -110,7 +155,7 @@ impl<'a> CommandJson<'a> {
 fn build_command_json<'a>(
     command: &'a CommandDesc,
     parent: Option<&str>,
-    out: &mut HashMap<String, CommandJson<'a>>,
+    out: &mut BTreeMap<String, CommandJson<'a>>,
 ) {
     let id = format!("c{}", command.id);
     for c in &command.commands {
@@ -119,6 +164,37 @@ fn build_command_json<'a>(
     out.insert(id, CommandJson::new(command, parent));
 }
 
+fn value_info_to_html(info: &ValueInfo) -> Option<String> {
+    if info.is_empty() {
+        return None;
+    }
+    let mut out = String::from("<table class=\"option-info\">");
+    for (label, value) in info.details() {
+        out.push_str("<tr><th>");
+        out.push_str(label);
+        out.push_str("</th><td>");
+        out.push_str(&escape_html(&value));
+        out.push_str("</td></tr>");
+    }
+    let values = info.described_values();
+    if !values.is_empty() {
+        out.push_str("<tr><th>Possible values</th><td>");
+        for value in values {
+            out.push_str("<div>");
+            out.push_str(&escape_html(&value.name));
+            if let Some(help) = &value.help {
+                out.push_str(" <span class=\"value-help\">");
+                out.push_str(&escape_html(help));
+                out.push_str("</span>");
+            }
+            out.push_str("</div>");
+        }
+        out.push_str("</td></tr>");
+    }
+    out.push_str("</table>");
+    Some(out)
+}
+
 fn escape_html(s: &str) -> String {
     let html = Html;
     let mut out = String::new();
@@ -134,15 +210,25 @@ fn build_command_tree<'a>(command: &'a CommandDesc, depth: u32) -> CommandTempla
         .collect();
     CommandTemplate {
         name: &command.name,
+        aliases: escape_html(&command.aliases.join(", ")),
+        failed: command.error.is_some(),
         id: command.id,
         subcommands,
         depth,
     }
 }
 
-pub fn render_html(program: &ProgramDesc) -> anyhow::Result<String> {
+/// Start of the element with the model, as written by `page.html`
+const MODEL_START: &str = "<script type=\"application/json\" id=\"cli-doc-model\">";
+
+/// `model_json` is the compact JSON output of the program before pruning of repeated options
+pub fn render_html(
+    program: &ProgramDesc,
+    model_json: &str,
+    theme: Option<&str>,
+) -> anyhow::Result<String> {
     let command_template = build_command_tree(&program.command, 0);
-    let mut command_jsons: HashMap<String, CommandJson> = Default::default();
+    let mut command_jsons: BTreeMap<String, CommandJson> = Default::default();
     build_command_json(&program.command, None, &mut command_jsons);
 
     Ok(PageTemplate {
@@ -150,7 +236,47 @@ pub fn render_html(program: &ProgramDesc) -> anyhow::Result<String> {
         version: &program.version,
         command: command_template,
         command_json: serde_json::to_string(&command_jsons)?,
+        // "<\/" is a valid JSON escape and it cannot close the script element
+        model_json: model_json.replace("</", "<\\/"),
         generator: format!("cli-doc {}", env!("CARGO_PKG_VERSION")),
+        theme,
     }
     .render()?)
 }
+
+/// Reads the model embedded in a page written by `render_html`
+pub fn parse_html(html: &str) -> anyhow::Result<ProgramDesc> {
+    let start = html.find(MODEL_START).ok_or_else(|| {
+        anyhow!("The page does not contain the model (it was generated by an older cli_doc)")
+    })? + MODEL_START.len();
+    let end = html[start..]
+        .find("</script>")
+        .ok_or_else(|| anyhow!("The model in the page is not terminated"))?;
+    parse_json(&html[start..start + end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::fixtures::{make_command, make_program, parse_clap};
+    use crate::json::render_compact_json;
+
+    #[test]
+    fn test_parse_html() {
+        let mut program = make_program(make_command("prog", vec![], vec![]));
+        program.command.doc = parse_clap(
+            "Fake program printing </script>
+
+Usage: prog [OPTIONS]
+
+Options:
+  -c, --color <WHEN>  Coloring [possible values: auto, always]
+  -h, --help          Print help
+",
+        );
+        let html = render_html(&program, &render_compact_json(&program).unwrap(), None).unwrap();
+        assert!(!html.contains("printing </script>"));
+        assert_eq!(parse_html(&html).unwrap(), program);
+        assert!(parse_html("<html></html>").is_err());
+    }
+}
@@ -0,0 +1,117 @@
+use crate::commands::ProgramDesc;
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+
+/// Version of the JSON model format; bumped on every incompatible change
+pub const JSON_FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize)]
+struct ProgramJsonRef<'a> {
+    format_version: u32,
+    generator: String,
+    program: &'a ProgramDesc,
+}
+
+#[derive(Deserialize)]
+struct ProgramJson {
+    format_version: u32,
+    program: ProgramDesc,
+}
+
+impl<'a> ProgramJsonRef<'a> {
+    fn new(program: &'a ProgramDesc) -> Self {
+        ProgramJsonRef {
+            format_version: JSON_FORMAT_VERSION,
+            generator: format!("cli-doc {}", env!("CARGO_PKG_VERSION")),
+            program,
+        }
+    }
+}
+
+pub fn render_json(program: &ProgramDesc) -> anyhow::Result<String> {
+    Ok(serde_json::to_string_pretty(&ProgramJsonRef::new(program))?)
+}
+
+/// The model without indentation, e.g. for embedding into an HTML page
+pub(crate) fn render_compact_json(program: &ProgramDesc) -> anyhow::Result<String> {
+    Ok(serde_json::to_string(&ProgramJsonRef::new(program))?)
+}
+
+pub fn parse_json(input: &str) -> anyhow::Result<ProgramDesc> {
+    let json: ProgramJson = serde_json::from_str(input)?;
+    if json.format_version != JSON_FORMAT_VERSION {
+        bail!(
+            "Unsupported format version {} (expected {})",
+            json.format_version,
+            JSON_FORMAT_VERSION
+        );
+    }
+    Ok(json.program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::fixtures::{make_command, make_option, make_program};
+    use crate::commands::{
+        ArgumentDesc, ExtractionError, PossibleValue, Usage, UsagePart, ValueInfo,
+    };
+    use crate::text::RichText;
+
+    fn make_model() -> ProgramDesc {
+        let mut description = RichText::new();
+        description.add_lines(&["Some text", "- item1", "- item2", "[default: 10]"]);
+        let mut option = make_option("--number <N>", "Number");
+        option.short = Some("-n".to_string());
+        option.description = Some(description);
+        option.info = ValueInfo {
+            value_name: Some("N".to_string()),
+            default: Some("1".to_string()),
+            possible_values: vec![
+                PossibleValue::new("1"),
+                PossibleValue {
+                    name: "2".to_string(),
+                    help: Some("Two".to_string()),
+                },
+            ],
+            env: Some("PROG_NUMBER".to_string()),
+            aliases: vec!["--num".to_string()],
+            value_hint: Some("number".to_string()),
+        };
+        let mut command = make_command("prog", vec![option], vec![]);
+        command.doc.usage = vec![Usage {
+            parts: vec![
+                UsagePart::Command("prog".to_string()),
+                UsagePart::Option("[OPTIONS]".to_string()),
+                UsagePart::Argument("<FILE>".to_string()),
+            ],
+        }];
+        command.doc.arguments = vec![ArgumentDesc {
+            name: "<FILE>".to_string(),
+            brief: RichText::from_single_line("Input file"),
+            description: None,
+            info: ValueInfo::default(),
+        }];
+        command.error = Some(ExtractionError {
+            message: "Invalid invocation of command with --help flag".to_string(),
+            exit_code: Some(2),
+            stderr: Some("error: unexpected argument".to_string()),
+        });
+        make_program(command)
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let program = make_model();
+        let json = render_json(&program).unwrap();
+        assert_eq!(parse_json(&json).unwrap(), program);
+    }
+
+    #[test]
+    fn test_json_invalid_version() {
+        let json = render_json(&make_model())
+            .unwrap()
+            .replace("\"format_version\": 1", "\"format_version\": 1000");
+        assert!(parse_json(&json).is_err());
+    }
+}
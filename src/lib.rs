@@ -1,15 +1,47 @@
+mod builder;
+#[cfg(feature = "clap-command")]
+mod clap_command;
 mod commands;
+mod config;
+mod diff;
 mod extractor;
 mod html;
+mod json;
+mod man;
+mod markdown;
+mod reporter;
 mod text;
 
-use crate::extractor::runner::collect_program_info;
-use crate::html::render_html;
-use std::path::Path;
+pub use crate::builder::{
+    Backend, DocBuilder, DocPage, Document, RenderOptions, render, render_with_options,
+};
+pub use crate::commands::{
+    ArgGroupDesc, ArgumentDesc, CategoryDesc, CommandDesc, CommandDoc, CommandId, CommandOuterDoc,
+    ExtractionError, OptionDesc, PossibleValue, ProgramDesc, Usage, UsagePart, ValueInfo,
+};
+pub use crate::config::{
+    CommandOverride, EnvironmentConfig, FilterConfig, OutputConfig, ProgramConfig, ProjectConfig,
+};
+pub use crate::diff::{ModelChange, diff_programs};
+pub use crate::extractor::capture::CaptureMode;
+pub use crate::extractor::environment::ProgramEnvironment;
+pub use crate::extractor::invocation::{HelpInvocation, OutputStream, VersionSource};
+pub use crate::extractor::parser::{BuiltinParser, HelpParser};
+pub use crate::extractor::runner::RunnerConfig;
+pub use crate::extractor::sections::Section;
+pub use crate::html::parse_html;
+pub use crate::json::{JSON_FORMAT_VERSION, parse_json};
+pub use crate::reporter::{ConsoleReporter, Invocation, Reporter, Verbosity};
+pub use crate::text::{RichText, RichTextPart};
 
-pub fn create_html_doc(path: &Path) -> anyhow::Result<String> {
-    let mut program = collect_program_info(path)?;
-    program.command.prune_repeated_options();
-    println!("Rendering HTML ...");
-    render_html(&program)
+#[cfg(feature = "clap-command")]
+pub use crate::clap_command::program_from_clap;
+
+/// Reads the model from the JSON output or from the HTML page of a program
+pub fn parse_model(input: &str) -> anyhow::Result<ProgramDesc> {
+    if input.trim_start().starts_with('{') {
+        parse_json(input)
+    } else {
+        parse_html(input)
+    }
 }
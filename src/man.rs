@@ -0,0 +1,348 @@
+use crate::DocPage;
+use crate::commands::{CommandDesc, ProgramDesc, UsagePart, ValueInfo};
+use crate::text::{RichText, RichTextPart, escape_roff};
+use std::fmt::Write;
+
+fn page_name(path: &[&str]) -> String {
+    path.join("-")
+}
+
+fn option_to_roff(option: &str) -> String {
+    let (flag, value) = option.split_once(' ').unwrap_or((option, ""));
+    let mut s = format!("\\fB{}\\fR", escape_roff(flag));
+    if !value.is_empty() {
+        write!(s, " \\fI{}\\fR", escape_roff(value)).unwrap();
+    }
+    s
+}
+
+fn write_tagged(
+    out: &mut String,
+    tag: &str,
+    brief: &RichText,
+    description: Option<&RichText>,
+    info: Option<&ValueInfo>,
+) {
+    writeln!(out, ".TP\n{tag}").unwrap();
+    let parts = brief
+        .parts
+        .iter()
+        .chain(description.iter().flat_map(|d| &d.parts));
+    let mut is_empty = true;
+    for part in parts {
+        // The first paragraph directly follows the tag
+        if is_empty {
+            part.to_roff_body_into(out);
+        } else {
+            part.to_roff_into(out, ".IP");
+        }
+        is_empty = false;
+    }
+    for (label, value) in info.map(|i| i.details()).unwrap_or_default() {
+        if !is_empty {
+            out.push_str(".br\n");
+        }
+        writeln!(
+            out,
+            "{}: \\fI{}\\fR",
+            escape_roff(label),
+            escape_roff(&value)
+        )
+        .unwrap();
+        is_empty = false;
+    }
+    let values = info.map(|i| i.described_values()).unwrap_or_default();
+    if !values.is_empty() {
+        if !is_empty {
+            out.push_str(".br\n");
+        }
+        out.push_str("Possible values:\n");
+        for value in values {
+            write!(out, ".br\n\\(bu \\fI{}\\fR", escape_roff(&value.name)).unwrap();
+            if let Some(help) = &value.help {
+                write!(out, ": {}", escape_roff(help)).unwrap();
+            }
+            out.push('\n');
+        }
+    }
+}
+
+fn render_command_page(
+    command: &CommandDesc,
+    path: &[&str],
+    program: &ProgramDesc,
+    out: &mut Vec<DocPage>,
+) {
+    let name = page_name(path);
+    let doc = &command.doc;
+    let mut s = String::new();
+    writeln!(
+        s,
+        ".TH \"{}\" \"1\" \"\" \"{}\" \"{} Manual\"",
+        escape_roff(&name.to_uppercase()),
+        escape_roff(&program.version),
+        escape_roff(&program.command.name)
+    )
+    .unwrap();
+
+    s.push_str(".SH NAME\n");
+    let brief = doc.brief.parts.iter().find_map(|part| match part {
+        RichTextPart::Text(text) => Some(text),
+        _ => None,
+    });
+    if let Some(brief) = brief {
+        writeln!(s, "{} \\- {}", escape_roff(&name), escape_roff(brief)).unwrap();
+    } else {
+        writeln!(s, "{}", escape_roff(&name)).unwrap();
+    }
+
+    if let Some(error) = &command.error {
+        writeln!(
+            s,
+            ".SH WARNING\nDocumentation of this command could not be extracted: {}",
+            escape_roff(error.message.lines().next().unwrap_or_default())
+        )
+        .unwrap();
+    }
+
+    if !doc.usage.is_empty() {
+        s.push_str(".SH SYNOPSIS\n");
+        for (i, usage) in doc.usage.iter().enumerate() {
+            if i > 0 {
+                s.push_str(".br\n");
+            }
+            let parts: Vec<_> = usage
+                .parts
+                .iter()
+                .map(|part| match part {
+                    UsagePart::Command(c) => format!("\\fB{}\\fR", escape_roff(c)),
+                    UsagePart::Argument(a) => format!("\\fI{}\\fR", escape_roff(a)),
+                    UsagePart::Option(o) => escape_roff(o),
+                })
+                .collect();
+            writeln!(s, "{}", parts.join(" ")).unwrap();
+        }
+    }
+
+    if !doc.brief.is_empty() || doc.description.is_some() {
+        s.push_str(".SH DESCRIPTION\n");
+        doc.brief.to_roff_into(&mut s, ".PP");
+        if let Some(description) = &doc.description {
+            description.to_roff_into(&mut s, ".PP");
+        }
+    }
+
+    if !doc.is_args_effectively_empty() {
+        s.push_str(".SH ARGUMENTS\n");
+        for arg in &doc.arguments {
+            let tag = format!("\\fI{}\\fR", escape_roff(&arg.name));
+            write_tagged(
+                &mut s,
+                &tag,
+                &arg.brief,
+                arg.description.as_ref(),
+                Some(&arg.info),
+            );
+        }
+    }
+
+    if !doc.option_categories.is_empty() {
+        s.push_str(".SH OPTIONS\n");
+        let with_subsections = doc.option_categories.len() > 1;
+        for category in &doc.option_categories {
+            if with_subsections {
+                writeln!(s, ".SS \"{}\"", escape_roff(&category.title)).unwrap();
+            }
+            for option in &category.options {
+                let tag = if let Some(short) = &option.short {
+                    format!(
+                        "{}, {}",
+                        option_to_roff(short),
+                        option_to_roff(&option.long)
+                    )
+                } else {
+                    option_to_roff(&option.long)
+                };
+                write_tagged(
+                    &mut s,
+                    &tag,
+                    &option.brief,
+                    option.description.as_ref(),
+                    Some(&option.info),
+                );
+            }
+        }
+    }
+
+    if !doc.groups.is_empty() {
+        s.push_str(".SH GROUPS\n");
+        for group in &doc.groups {
+            let members: Vec<_> = group
+                .members
+                .iter()
+                .map(|m| {
+                    if m.starts_with('-') {
+                        option_to_roff(m)
+                    } else {
+                        format!("\\fI{}\\fR", escape_roff(m))
+                    }
+                })
+                .collect();
+            writeln!(s, ".PP\n{} {}", group.constraint(), members.join(", ")).unwrap();
+        }
+    }
+
+    if !command.commands.is_empty() {
+        s.push_str(".SH COMMANDS\n");
+        for child in &command.commands {
+            let mut child_path = path.to_vec();
+            child_path.push(&child.name);
+            let tag = format!("\\fB{}\\fR(1)", escape_roff(&page_name(&child_path)));
+            write_tagged(&mut s, &tag, &child.doc.brief, None, None);
+        }
+    }
+
+    if path.len() == 1 {
+        writeln!(s, ".SH VERSION\n{}", escape_roff(&program.version)).unwrap();
+    }
+
+    let mut see_also = Vec::new();
+    if path.len() > 1 {
+        see_also.push(page_name(&path[..path.len() - 1]));
+    }
+    for child in &command.commands {
+        let mut child_path = path.to_vec();
+        child_path.push(&child.name);
+        see_also.push(page_name(&child_path));
+    }
+    if !see_also.is_empty() {
+        s.push_str(".SH \"SEE ALSO\"\n");
+        let links: Vec<_> = see_also
+            .iter()
+            .map(|name| format!("\\fB{}\\fR(1)", escape_roff(name)))
+            .collect();
+        writeln!(s, "{}", links.join(", ")).unwrap();
+    }
+
+    out.push(DocPage {
+        filename: format!("{name}.1"),
+        content: s,
+    });
+}
+
+fn render_man_helper<'a>(
+    command: &'a CommandDesc,
+    path: &mut Vec<&'a str>,
+    program: &ProgramDesc,
+    out: &mut Vec<DocPage>,
+) {
+    path.push(&command.name);
+    render_command_page(command, path, program, out);
+    for child in &command.commands {
+        render_man_helper(child, path, program, out);
+    }
+    path.pop();
+}
+
+/// Renders one man page (section 1) per command
+pub fn render_man_pages(program: &ProgramDesc) -> Vec<DocPage> {
+    let mut pages = Vec::new();
+    render_man_helper(&program.command, &mut Vec::new(), program, &mut pages);
+    pages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::fixtures::{make_command, make_program, parse_clap};
+
+    #[test]
+    fn test_render_man_pages() {
+        let main = parse_clap(
+            "Fake program
+
+Usage: prog [OPTIONS] <COMMAND>
+
+Options:
+  -c, --color <WHEN>
+          Coloring
+
+          [possible values: auto, always]
+
+  -h, --help
+          Print help
+
+Other:
+      --dry-run  Do nothing
+",
+        );
+        let run = parse_clap(
+            "Run something
+
+Usage: prog run <FILE>
+
+Arguments:
+  <FILE>  File to run
+",
+        );
+        let mut program = make_program(make_command(
+            "prog",
+            vec![],
+            vec![make_command("run", vec![], vec![])],
+        ));
+        program.command.doc = main;
+        program.command.commands[0].doc = run;
+        let pages = render_man_pages(&program);
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].filename, "prog.1");
+        insta::assert_snapshot!(pages[0].content, @r#"
+        .TH "PROG" "1" "" "prog 1.0" "prog Manual"
+        .SH NAME
+        prog \- Fake program
+        .SH SYNOPSIS
+        \fBprog\fR [OPTIONS] \fI<COMMAND>\fR
+        .SH DESCRIPTION
+        .PP
+        Fake program
+        .SH OPTIONS
+        .SS "Options"
+        .TP
+        \fB\-c\fR, \fB\-\-color\fR \fI<WHEN>\fR
+        Coloring
+        .br
+        Possible values: \fIauto, always\fR
+        .TP
+        \fB\-h\fR, \fB\-\-help\fR
+        Print help
+        .SS "Other"
+        .TP
+        \fB\-\-dry\-run\fR
+        Do nothing
+        .SH COMMANDS
+        .TP
+        \fBprog\-run\fR(1)
+        Run something
+        .SH VERSION
+        prog 1.0
+        .SH "SEE ALSO"
+        \fBprog\-run\fR(1)
+        "#);
+        assert_eq!(pages[1].filename, "prog-run.1");
+        insta::assert_snapshot!(pages[1].content, @r#"
+        .TH "PROG\-RUN" "1" "" "prog 1.0" "prog Manual"
+        .SH NAME
+        prog\-run \- Run something
+        .SH SYNOPSIS
+        \fBprog\fR \fBrun\fR \fI<FILE>\fR
+        .SH DESCRIPTION
+        .PP
+        Run something
+        .SH ARGUMENTS
+        .TP
+        \fI<FILE>\fR
+        File to run
+        .SH "SEE ALSO"
+        \fBprog\fR(1)
+        "#);
+    }
+}
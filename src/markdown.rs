@@ -0,0 +1,444 @@
+use crate::DocPage;
+use crate::commands::{CommandDesc, ProgramDesc, ValueInfo};
+use crate::text::{RichText, escape_markdown};
+use std::fmt::Write;
+
+struct MarkdownWriter<'a> {
+    out: String,
+    /// Link subcommands to their own files instead of headings in the same document
+    split: bool,
+    path: Vec<&'a str>,
+}
+
+impl<'a> MarkdownWriter<'a> {
+    fn new(split: bool) -> Self {
+        MarkdownWriter {
+            out: String::new(),
+            split,
+            path: Vec::new(),
+        }
+    }
+
+    fn heading(&mut self, level: usize, title: &str) {
+        writeln!(self.out, "{} {}\n", "#".repeat(level.min(6)), title).unwrap();
+    }
+
+    fn table_header(&mut self, first: &str) {
+        writeln!(self.out, "| {first} | Description |\n| --- | --- |").unwrap();
+    }
+
+    fn write_command(&mut self, command: &'a CommandDesc, level: usize) {
+        let full_name = self.path.join(" ");
+        self.heading(level, &format!("`{full_name}`"));
+
+        if let Some(error) = &command.error {
+            writeln!(
+                self.out,
+                "> **Warning:** Documentation could not be extracted: {}\n",
+                escape_markdown(error.message.lines().next().unwrap_or_default())
+            )
+            .unwrap();
+        }
+
+        let doc = &command.doc;
+        self.out.push_str(&doc.brief.to_markdown());
+        if let Some(description) = &doc.description {
+            self.out.push_str(&description.to_markdown());
+        }
+
+        if !doc.usage.is_empty() {
+            self.out.push_str("**Usage:**\n\n```text\n");
+            for usage in &doc.usage {
+                writeln!(self.out, "{}", usage.to_text()).unwrap();
+            }
+            self.out.push_str("```\n\n");
+        }
+
+        if !doc.is_args_effectively_empty() {
+            self.heading(level + 1, "Arguments");
+            self.table_header("Argument");
+            for arg in &doc.arguments {
+                writeln!(
+                    self.out,
+                    "| {} | {} |",
+                    table_code(&arg.name),
+                    full_description(&arg.brief, arg.description.as_ref(), &arg.info)
+                )
+                .unwrap();
+            }
+            self.out.push('\n');
+        }
+
+        for category in &doc.option_categories {
+            self.heading(level + 1, &escape_markdown(&category.title));
+            self.table_header("Option");
+            for option in &category.options {
+                let name = if let Some(short) = &option.short {
+                    format!("{}, {}", table_code(short), table_code(&option.long))
+                } else {
+                    table_code(&option.long)
+                };
+                writeln!(
+                    self.out,
+                    "| {} | {} |",
+                    name,
+                    full_description(&option.brief, option.description.as_ref(), &option.info)
+                )
+                .unwrap();
+            }
+            self.out.push('\n');
+        }
+
+        if !doc.groups.is_empty() {
+            self.heading(level + 1, "Groups");
+            for group in &doc.groups {
+                let members: Vec<_> = group.members.iter().map(|m| format!("`{m}`")).collect();
+                writeln!(self.out, "- {} {}", group.constraint(), members.join(", ")).unwrap();
+            }
+            self.out.push('\n');
+        }
+
+        if !command.commands.is_empty() {
+            self.heading(level + 1, "Subcommands");
+            self.table_header("Command");
+            for child in &command.commands {
+                let link = if self.split {
+                    let mut path = self.path.clone();
+                    path.push(&child.name);
+                    page_link(&path)
+                } else {
+                    let full_name = format!("{} {}", self.path.join(" "), child.name);
+                    format!("#{}", heading_anchor(&full_name))
+                };
+                writeln!(
+                    self.out,
+                    "| [{}]({}) | {} |",
+                    table_code(&child.name),
+                    link,
+                    child.doc.brief.to_markdown_inline()
+                )
+                .unwrap();
+            }
+            self.out.push('\n');
+        }
+    }
+}
+
+fn full_description(brief: &RichText, description: Option<&RichText>, info: &ValueInfo) -> String {
+    let mut s = brief.to_markdown_inline();
+    if let Some(description) = description {
+        if !s.is_empty() {
+            s.push_str("<br>");
+        }
+        s.push_str(&description.to_markdown_inline());
+    }
+    for (label, value) in info.details() {
+        if !s.is_empty() {
+            s.push_str("<br>");
+        }
+        write!(s, "*{}:* {}", label, table_code(&value)).unwrap();
+    }
+    let values = info.described_values();
+    if !values.is_empty() {
+        if !s.is_empty() {
+            s.push_str("<br>");
+        }
+        s.push_str("*Possible values:*");
+        for value in values {
+            write!(s, "<br>• {}", table_code(&value.name)).unwrap();
+            if let Some(help) = &value.help {
+                write!(s, ": {}", escape_markdown(help)).unwrap();
+            }
+        }
+    }
+    s
+}
+
+/// Code span inside a table cell; `|` is escaped even in code spans in GFM tables and
+/// the fence is longer than any run of backticks in the text
+fn table_code(text: &str) -> String {
+    let longest_run = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run + 1);
+    let text = text.replace('|', "\\|");
+    // A space separates backticks of the text from the fence
+    if text.starts_with('`') || text.ends_with('`') {
+        format!("{fence} {text} {fence}")
+    } else {
+        format!("{fence}{text}{fence}")
+    }
+}
+
+/// Anchor of a heading as generated by GitHub: lowercase, punctuation except `-` and `_`
+/// removed and spaces replaced by `-`, e.g. "hsh-sub" for "h.sh sub"
+fn heading_anchor(title: &str) -> String {
+    title
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn page_filename(path: &[&str]) -> String {
+    format!("{}.md", path.join("-"))
+}
+
+/// Destination of a link to the page; a space would end the destination
+fn page_link(path: &[&str]) -> String {
+    page_filename(path).replace(' ', "%20")
+}
+
+fn write_commands_recursively<'a>(
+    writer: &mut MarkdownWriter<'a>,
+    command: &'a CommandDesc,
+    level: usize,
+) {
+    writer.path.push(&command.name);
+    writer.write_command(command, level);
+    for child in &command.commands {
+        write_commands_recursively(writer, child, level);
+    }
+    writer.path.pop();
+}
+
+/// Renders the whole program into a single Markdown document
+pub fn render_markdown(program: &ProgramDesc) -> String {
+    let mut writer = MarkdownWriter::new(false);
+    writer.heading(1, &escape_markdown(&program.command.name));
+    writeln!(writer.out, "Version: `{}`\n", program.version).unwrap();
+    write_commands_recursively(&mut writer, &program.command, 2);
+    writer.out
+}
+
+fn render_pages_helper<'a>(
+    command: &'a CommandDesc,
+    version: &str,
+    path: &mut Vec<&'a str>,
+    out: &mut Vec<DocPage>,
+) {
+    path.push(&command.name);
+    let mut writer = MarkdownWriter::new(true);
+    writer.path = path.clone();
+    writer.write_command(command, 1);
+    if path.len() == 1 {
+        writeln!(writer.out, "Version: `{version}`").unwrap();
+    } else {
+        let parent = &path[..path.len() - 1];
+        writeln!(
+            writer.out,
+            "Parent command: [`{}`]({})",
+            parent.join(" "),
+            page_link(parent)
+        )
+        .unwrap();
+    }
+    out.push(DocPage {
+        filename: page_filename(path),
+        content: writer.out,
+    });
+    for child in &command.commands {
+        render_pages_helper(child, version, path, out);
+    }
+    path.pop();
+}
+
+/// Renders one Markdown document per command
+pub fn render_markdown_pages(program: &ProgramDesc) -> Vec<DocPage> {
+    let mut pages = Vec::new();
+    render_pages_helper(
+        &program.command,
+        &program.version,
+        &mut Vec::new(),
+        &mut pages,
+    );
+    pages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::fixtures::{self, make_command, parse_clap};
+    use crate::commands::{ArgGroupDesc, ExtractionError};
+
+    fn make_program() -> ProgramDesc {
+        let main = parse_clap(
+            "Fake program
+
+Usage: prog [OPTIONS] <COMMAND>
+
+Commands:
+  run   Run something
+  help  Print this message or the help of the given subcommand(s)
+
+Options:
+  -c, --color <WHEN>  Coloring [possible values: auto, always]
+  -h, --help          Print help
+",
+        );
+        let run = parse_clap(
+            "Run something
+
+Usage: prog run <FILE>
+
+Arguments:
+  <FILE>  File to run
+",
+        );
+        let mut program = fixtures::make_program(make_command(
+            "prog",
+            vec![],
+            vec![make_command("run", vec![], vec![])],
+        ));
+        program.command.doc = main;
+        program.command.commands[0].doc = run;
+        program
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        insta::assert_snapshot!(render_markdown(&make_program()), @"
+        # prog
+
+        Version: `prog 1.0`
+
+        ## `prog`
+
+        Fake program
+
+        **Usage:**
+
+        ```text
+        prog [OPTIONS] <COMMAND>
+        ```
+
+        ### Options
+
+        | Option | Description |
+        | --- | --- |
+        | `-c`, `--color <WHEN>` | Coloring<br>*Possible values:* `auto, always` |
+        | `-h`, `--help` | Print help |
+
+        ### Subcommands
+
+        | Command | Description |
+        | --- | --- |
+        | [`run`](#prog-run) | Run something |
+
+        ## `prog run`
+
+        Run something
+
+        **Usage:**
+
+        ```text
+        prog run <FILE>
+        ```
+
+        ### Arguments
+
+        | Argument | Description |
+        | --- | --- |
+        | `<FILE>` | File to run |
+        ");
+    }
+
+    #[test]
+    fn test_render_markdown_pages() {
+        let pages = render_markdown_pages(&make_program());
+        assert_eq!(
+            pages
+                .iter()
+                .map(|p| p.filename.as_str())
+                .collect::<Vec<_>>(),
+            vec!["prog.md", "prog-run.md"]
+        );
+        assert!(pages[0].content.contains("[`run`](prog-run.md)"));
+        assert!(pages[1].content.starts_with("# `prog run`\n"));
+        assert!(
+            pages[1]
+                .content
+                .ends_with("Parent command: [`prog`](prog.md)\n")
+        );
+    }
+
+    #[test]
+    fn test_render_markdown_failed_command() {
+        let mut program = make_program();
+        program.command.commands[0].error = Some(ExtractionError {
+            message: "Running `prog run --help` failed\n\nProcess timed out after 30 s".to_string(),
+            exit_code: None,
+            stderr: None,
+        });
+        let output = render_markdown(&program);
+        assert!(output.contains(
+            "## `prog run`\n\n> **Warning:** Documentation could not be extracted: Running \\`prog run --help\\` failed\n"
+        ));
+    }
+
+    #[test]
+    fn test_subcommand_anchors() {
+        for (name, anchor) in [("h.sh", "#hsh-sub"), ("My Tool", "#my-tool-sub")] {
+            let program = fixtures::make_program(make_command(
+                name,
+                vec![],
+                vec![make_command("sub", vec![], vec![])],
+            ));
+            assert!(render_markdown(&program).contains(&format!("[`sub`]({anchor})")));
+        }
+        let program = fixtures::make_program(make_command(
+            "My Tool",
+            vec![],
+            vec![make_command("sub", vec![], vec![])],
+        ));
+        let pages = render_markdown_pages(&program);
+        assert!(pages[0].content.contains("[`sub`](My%20Tool-sub.md)"));
+    }
+
+    #[test]
+    fn test_table_code() {
+        assert_eq!(table_code("--color <WHEN>"), "`--color <WHEN>`");
+        assert_eq!(
+            table_code("--color <auto|always>"),
+            "`--color <auto\\|always>`"
+        );
+        assert_eq!(table_code("{a|b}"), "`{a\\|b}`");
+        assert_eq!(table_code("a`b"), "``a`b``");
+        assert_eq!(table_code("`x`"), "`` `x` ``");
+
+        let mut program = make_program();
+        program.command.doc.option_categories[0].options[0].long = "--color <auto|always>".into();
+        assert!(render_markdown(&program).contains(
+            "| `-c`, `--color <auto\\|always>` | Coloring<br>*Possible values:* `auto, always` |"
+        ));
+    }
+
+    #[test]
+    fn test_render_described_values() {
+        let mut program = make_program();
+        let info = &mut program.command.doc.option_categories[0].options[0].info;
+        info.possible_values[1].help = Some("Even | when piped".to_string());
+        let output = render_markdown(&program);
+        assert!(output.contains(
+            "| `-c`, `--color <WHEN>` | Coloring<br>*Possible values:*<br>• `auto`<br>• `always`: Even \\| when piped |"
+        ));
+    }
+
+    #[test]
+    fn test_render_groups() {
+        let mut program = make_program();
+        program.command.doc.groups.push(ArgGroupDesc {
+            name: "output".to_string(),
+            members: vec!["--json".to_string(), "[FILE]".to_string()],
+            required: true,
+            multiple: false,
+        });
+        assert!(
+            render_markdown(&program)
+                .contains("### Groups\n\n- Exactly one of `--json`, `[FILE]`\n\n### Subcommands")
+        );
+    }
+}
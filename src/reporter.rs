@@ -0,0 +1,178 @@
+use colored::Colorize;
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+
+/// A single invocation of the documented program
+pub struct Invocation<'a> {
+    pub program: &'a Path,
+    /// Fixed arguments of the program (e.g. of a launcher)
+    pub program_args: &'a [String],
+    /// Arguments of the command and the help (or version) flag
+    pub args: &'a [String],
+    /// The output is read from a capture instead of running the program
+    pub replayed: bool,
+}
+
+impl fmt::Display for Invocation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.program.display())?;
+        for arg in self.program_args.iter().chain(self.args) {
+            write!(f, " {arg}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Receiver of the progress of extraction and rendering; all methods do nothing by default
+///
+/// Methods are called from multiple threads when commands are extracted in parallel.
+pub trait Reporter: Send + Sync {
+    /// The program is about to be run (or its output replayed)
+    fn command_started(&self, _invocation: &Invocation) {}
+
+    /// The program printed its help or version
+    fn command_finished(&self, _invocation: &Invocation, _elapsed: Duration) {}
+
+    /// The program finished with an error
+    fn command_failed(&self, _invocation: &Invocation, _exit_code: Option<i32>, _stderr: &str) {}
+
+    /// The program could not be run, did not finish in time or its output is not in
+    /// the replayed capture
+    fn command_error(&self, _invocation: &Invocation, _error: &str) {}
+
+    /// A subcommand is not extracted because of filters; `path` does not contain the program
+    fn command_skipped(&self, _program: &str, _path: &[String]) {}
+
+    /// A problem that does not stop the extraction
+    fn warning(&self, _message: &str) {}
+
+    /// Outputs of the program were stored into the capture file
+    fn capture_saved(&self, _path: &Path) {}
+
+    /// The documentation is being rendered in the format (e.g. "HTML")
+    fn rendering(&self, _format: &str) {}
+
+    /// An obsolete page was removed from the output directory
+    fn file_removed(&self, _path: &Path) {}
+}
+
+/// Amount of messages printed by `ConsoleReporter`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Only warnings
+    Quiet,
+    /// Invocations of the program, failed commands with their error output, warnings
+    /// and rendering
+    #[default]
+    Normal,
+    /// Also durations of invocations
+    Verbose,
+}
+
+/// Colored progress printed to stderr, so stdout stays free for the documentation
+#[derive(Debug, Default)]
+pub struct ConsoleReporter {
+    verbosity: Verbosity,
+}
+
+impl ConsoleReporter {
+    pub fn new(verbosity: Verbosity) -> Self {
+        ConsoleReporter { verbosity }
+    }
+
+    fn format_invocation(invocation: &Invocation) -> String {
+        let mut line = invocation.program.display().to_string().cyan().to_string();
+        for arg in invocation.program_args {
+            line.push_str(&format!(" {}", arg.cyan()));
+        }
+        for arg in invocation.args {
+            line.push_str(&format!(" {}", arg.yellow()));
+        }
+        line
+    }
+}
+
+impl Reporter for ConsoleReporter {
+    fn command_started(&self, invocation: &Invocation) {
+        if self.verbosity >= Verbosity::Normal {
+            // The line is printed at once, so it is not mixed with lines of other jobs
+            eprintln!(
+                "{} {}",
+                if invocation.replayed {
+                    "Replaying"
+                } else {
+                    "Running"
+                },
+                Self::format_invocation(invocation)
+            );
+        }
+    }
+
+    fn command_finished(&self, invocation: &Invocation, elapsed: Duration) {
+        if self.verbosity >= Verbosity::Verbose {
+            eprintln!(
+                "Finished {} in {:.2} s",
+                Self::format_invocation(invocation),
+                elapsed.as_secs_f64()
+            );
+        }
+    }
+
+    fn command_failed(&self, invocation: &Invocation, exit_code: Option<i32>, stderr: &str) {
+        if self.verbosity >= Verbosity::Normal {
+            let code = exit_code.map_or("none".to_string(), |c| c.to_string());
+            eprintln!(
+                "{} {} (exit code {})",
+                "Failed".red(),
+                Self::format_invocation(invocation),
+                code
+            );
+            for line in stderr.lines() {
+                eprintln!("  {line}");
+            }
+        }
+    }
+
+    fn command_error(&self, invocation: &Invocation, error: &str) {
+        if self.verbosity >= Verbosity::Normal {
+            eprintln!(
+                "{} {}: {}",
+                "Failed".red(),
+                Self::format_invocation(invocation),
+                error
+            );
+        }
+    }
+
+    fn command_skipped(&self, program: &str, path: &[String]) {
+        if self.verbosity >= Verbosity::Normal {
+            eprintln!("Skipping {} {}", program.cyan(), path.join(" ").yellow());
+        }
+    }
+
+    fn warning(&self, message: &str) {
+        eprintln!("{} {}", "Warning:".yellow(), message);
+    }
+
+    fn capture_saved(&self, path: &Path) {
+        if self.verbosity >= Verbosity::Normal {
+            eprintln!(
+                "Capture written into: {}",
+                path.display().to_string().green()
+            );
+        }
+    }
+
+    fn rendering(&self, format: &str) {
+        if self.verbosity >= Verbosity::Normal {
+            eprintln!("Rendering {format} ...");
+        }
+    }
+
+    fn file_removed(&self, path: &Path) {
+        if self.verbosity >= Verbosity::Normal {
+            eprintln!("Removed {}", path.display().to_string().yellow());
+        }
+    }
+}
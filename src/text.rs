@@ -1,11 +1,13 @@
 use askama::filters::{Escaper, Html};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct RichText {
     pub parts: Vec<RichTextPart>,
 }
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum RichTextPart {
     Text(String),
     Ul(Vec<String>),
@@ -39,6 +41,85 @@ impl RichTextPart {
             }
         }
     }
+
+    pub fn to_markdown_into(&self, out: &mut String) {
+        match self {
+            RichTextPart::Text(s) => {
+                out.push_str(&escape_markdown(s));
+                out.push_str("\n\n");
+            }
+            RichTextPart::Ul(items) => {
+                for item in items {
+                    out.push_str("- ");
+                    out.push_str(&escape_markdown(item));
+                    out.push('\n');
+                }
+                out.push('\n');
+            }
+            RichTextPart::Config { key, value } => {
+                out.push_str(&format!(
+                    "\\[{}: {}\\]\n\n",
+                    escape_markdown(key),
+                    escape_markdown(value)
+                ));
+            }
+        }
+    }
+
+    pub fn to_markdown_inline_into(&self, out: &mut String) {
+        match self {
+            RichTextPart::Text(s) => out.push_str(&escape_markdown(s)),
+            RichTextPart::Ul(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str("<br>");
+                    }
+                    out.push_str("• ");
+                    out.push_str(&escape_markdown(item));
+                }
+            }
+            RichTextPart::Config { key, value } => {
+                out.push_str(&format!(
+                    "\\[{}: {}\\]",
+                    escape_markdown(key),
+                    escape_markdown(value)
+                ));
+            }
+        }
+    }
+
+    /// `par` is the roff macro starting a paragraph (`.PP`, or `.IP` inside an indented block)
+    pub fn to_roff_into(&self, out: &mut String, par: &str) {
+        out.push_str(par);
+        out.push('\n');
+        self.to_roff_body_into(out);
+    }
+
+    pub fn to_roff_body_into(&self, out: &mut String) {
+        match self {
+            RichTextPart::Text(s) => {
+                out.push_str(&escape_roff(s));
+                out.push('\n');
+            }
+            RichTextPart::Ul(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(".br\n");
+                    }
+                    out.push_str("\\(bu ");
+                    out.push_str(&escape_roff(item));
+                    out.push('\n');
+                }
+            }
+            RichTextPart::Config { key, value } => {
+                out.push_str(&format!(
+                    "[{}: \\fI{}\\fR]\n",
+                    escape_roff(key),
+                    escape_roff(value)
+                ));
+            }
+        }
+    }
 }
 
 impl RichText {
@@ -117,4 +198,102 @@ impl RichText {
         s.push_str("</div>");
         s
     }
+
+    pub fn to_markdown(&self) -> String {
+        let mut s = String::new();
+        for part in &self.parts {
+            part.to_markdown_into(&mut s);
+        }
+        s
+    }
+
+    pub fn to_roff_into(&self, out: &mut String, par: &str) {
+        for part in &self.parts {
+            part.to_roff_into(out, par);
+        }
+    }
+
+    /// Plain text with one line per paragraph, list item or config
+    pub fn to_text(&self) -> String {
+        let mut lines = Vec::new();
+        for part in &self.parts {
+            match part {
+                RichTextPart::Text(s) => lines.push(s.clone()),
+                RichTextPart::Ul(items) => lines.extend(items.iter().map(|i| format!("- {i}"))),
+                RichTextPart::Config { key, value } => lines.push(format!("[{key}: {value}]")),
+            }
+        }
+        lines.join("\n")
+    }
+
+    /// Renders the text on a single line, e.g. for a table cell
+    pub fn to_markdown_inline(&self) -> String {
+        let mut s = String::new();
+        for (i, part) in self.parts.iter().enumerate() {
+            if i > 0 {
+                s.push_str("<br>");
+            }
+            part.to_markdown_inline_into(&mut s);
+        }
+        s
+    }
+}
+
+pub fn escape_roff(text: &str) -> String {
+    let text = text.replace('\\', "\\e").replace('-', "\\-");
+    if text.starts_with('.') || text.starts_with('\'') {
+        format!("\\&{text}")
+    } else {
+        text
+    }
+}
+
+pub fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '#'
+        ) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rich_text_to_markdown() {
+        let mut text = RichText::new();
+        text.add_lines(&[
+            "Use <FILE> as input",
+            "- first_item",
+            "- second",
+            "[default: a|b]",
+        ]);
+        assert_eq!(
+            text.to_markdown(),
+            "Use \\<FILE\\> as input\n\n- first\\_item\n- second\n\n\\[default: a\\|b\\]\n\n"
+        );
+        assert_eq!(
+            text.to_markdown_inline(),
+            "Use \\<FILE\\> as input<br>• first\\_item<br>• second<br>\\[default: a\\|b\\]"
+        );
+    }
+
+    #[test]
+    fn test_rich_text_to_roff() {
+        let mut text = RichText::new();
+        text.add_lines(&[".hidden-file \\path", "- one", "- two", "[default: x-y]"]);
+        let mut out = String::new();
+        text.to_roff_into(&mut out, ".PP");
+        assert_eq!(
+            out,
+            ".PP\n\\&.hidden\\-file \\epath\n.PP\n\\(bu one\n.br\n\\(bu two\n.PP\n[default: \\fIx\\-y\\fR]\n"
+        );
+    }
 }
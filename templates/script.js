@@ -54,9 +54,27 @@ function showCommandDetails(command) {
         desc = `<div style="margin-top: 8px;">${data.description}</div>`
     }
 
+    let aliases = "";
+    if (data.aliases) {
+        aliases = `<div class="info-aliases">Aliases: ${data.aliases.map(a => `<span class="command-alias">${a}</span>`).join(", ")}</div>`
+    }
+
+    let error = "";
+    if (data.error) {
+        let details = "";
+        if (data.error.exit_code !== null) {
+            details += `<div>Exit code: ${data.error.exit_code}</div>`;
+        }
+        if (data.error.stderr) {
+            details += `<pre>${data.error.stderr}</pre>`;
+        }
+        error = `<div class="command-error"><b>⚠ Documentation could not be extracted</b><pre>${data.error.message}</pre>${details}</div>`
+    }
+
     document.getElementById('commandInfo').innerHTML = `
+                ${error}
                  <div class="info-brief">${data.brief}</div>
-                ${usages} ${desc}
+                ${aliases} ${usages} ${desc}
             `;
 
     if (data.arguments.length === 0) {
@@ -78,6 +96,7 @@ function showCommandDetails(command) {
                         <div class="option-main">
                           <div class="argument-name">${argument.name}</div>
                           <div class="option-description">${argument.brief}</div>
+                          ${argument.info ?? ""}
                         </div>
                         ${showMore}
                     </div>
@@ -119,6 +138,7 @@ function showCommandDetails(command) {
                                         <div class="option-main">
                                           <div class="option-name">${short}${option.long}</div>
                                           <div class="option-description">${option.brief}</div>
+                                          ${option.info ?? ""}
                                         </div>
                                         ${showMore}
                                     </div>
@@ -136,6 +156,23 @@ function showCommandDetails(command) {
             }
         ).join("");
     }
+
+    const groups = data.groups ?? [];
+    if (groups.length === 0) {
+        document.getElementById('groupsInfo').innerHTML = '';
+    } else {
+        let items = groups.map(group => `<div class="option-item">
+                    <div class="option-description">${group}</div>
+                </div>`);
+        document.getElementById('groupsInfo').innerHTML = `<div class="info-section">
+                    <div class="info-header">
+                        Groups
+                    </div>
+                    <div class="info-content">
+                        ${items.join("")}
+                    </div>
+                </div>`;
+    }
 }
 
 function expandCommandsTo(id) {
@@ -157,7 +194,7 @@ function performSearch() {
     const keys = Object.keys(commandData).toSorted();
     keys.forEach(command => {
         const data = commandData[command];
-        if (data.name.toLowerCase().includes(searchTerm)) {
+        if (data.name.toLowerCase().includes(searchTerm) || (data.aliases ?? []).some(a => a.toLowerCase().includes(searchTerm))) {
             results.push({command: command});
         }
         data.categories.forEach(category => {
@@ -255,7 +292,7 @@ function init() {
         if (current.children) {
             for (const child_id of current.children) {
                 let child = commandData[child_id];
-                if (child.name === part) {
+                if (child.name === part || (child.aliases ?? []).includes(part)) {
                     found = true;
                     currentId = child_id;
                     break